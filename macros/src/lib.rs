@@ -1,13 +1,17 @@
 use proc_macro::TokenStream;
 use quote::quote;
 use std::collections::HashMap;
-use syn::{Attribute, Data, DeriveInput, Fields, Meta, Token, Type, parse_macro_input};
+use syn::{
+    Attribute, Data, DeriveInput, Fields, Meta, Token, Type, ext::IdentExt, parse_macro_input,
+};
 
 /// Helper enum for parsed attribute values
 enum MetaValue {
     Str(String),
     Expr(syn::Expr),
     Flag,
+    /// `key(name = "value", ...)`
+    List(HashMap<String, String>),
 }
 
 /// Check if the struct has #[allow(missing_docs)] attribute
@@ -41,6 +45,7 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
 
     // Check for struct-level attributes
     let allow_missing_docs = check_allow_missing_docs(struct_attrs);
+    let struct_config = parse_struct_config(struct_attrs)?;
 
     // Extract fields from the struct
     let fields = match &input.data {
@@ -65,8 +70,11 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
     let mut meta_field_defs = Vec::new(); // For ConfigMeta struct (metadata)
     let mut load_impl_fields = Vec::new();
     let mut load_impl_unwraps = Vec::new();
-    let mut docs_impl_fields = Vec::new();
     let mut meta_field_inits = Vec::new(); // For initializing ConfigMeta fields
+    let mut environment_fields = Vec::new(); // Fields of type Environment, for dotenv_cascade
+    let mut env_field_names = Vec::new(); // Fields read from a variable, for field groups
+    let mut debug_fields = Vec::new(); // For a Debug impl that redacts secret fields
+    let mut redacts_debug = false;
 
     for field in fields {
         let field_name = field
@@ -78,7 +86,13 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
         let field_attrs = &field.attrs;
 
         // Parse field configuration from attributes
-        let config = parse_field_config(field_attrs, allow_missing_docs)?;
+        let config = parse_field_config(field_attrs, allow_missing_docs, &struct_config.prefix)?;
+        if !matches!(
+            config.mode,
+            FieldMode::Nested { .. } | FieldMode::Repeated { .. }
+        ) {
+            env_field_names.push(field_name.unraw().to_string());
+        }
 
         // Extract cfg attributes for feature gating
         let cfg_attrs: Vec<&Attribute> = field_attrs
@@ -86,6 +100,21 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
             .filter(|attr| attr.path().is_ident("cfg"))
             .collect();
 
+        // Secret fields of a type that doesn't redact itself are redacted by Debug
+        let name_str = field_name.unraw().to_string();
+        if config.secret && !is_secret_type(extract_option_type(field_type).1) {
+            redacts_debug = true;
+            debug_fields.push(quote! {
+                #(#cfg_attrs)*
+                debug.field(#name_str, &format_args!("{}", ::config_loadr::secret::REDACTED));
+            });
+        } else {
+            debug_fields.push(quote! {
+                #(#cfg_attrs)*
+                debug.field(#name_str, &self.#field_name);
+            });
+        }
+
         // Generate direct value field for Config struct
         let inner_type = extract_inner_type(field_type);
         value_field_defs.push(quote! {
@@ -93,6 +122,51 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
             #field_vis #field_name: #field_type
         });
 
+        // Nested configs load through the same builder under their own scope
+        if let FieldMode::Nested { prefix } | FieldMode::Repeated { prefix } = &config.mode {
+            let name_str = field_name.unraw().to_string();
+            let (child_type, load, meta_prefix) = match &config.mode {
+                FieldMode::Repeated { .. } => {
+                    let child_type = extract_vec_type(field_type).ok_or_else(|| {
+                        syn::Error::new_spanned(field, "repeated fields must have type Vec<T>")
+                    })?;
+                    let load = quote! {
+                        builder.repeated(#prefix, #name_str, <#child_type as ::config_loadr::Load>::load_into)
+                    };
+                    (child_type, load, format!("{}<n>_", prefix))
+                }
+                _ => {
+                    let load = quote! {{
+                        builder.push_scope(#prefix, #name_str);
+                        let #field_name = <#field_type as ::config_loadr::Load>::load_into(builder);
+                        builder.pop_scope();
+                        #field_name
+                    }};
+                    (field_type, load, prefix.clone())
+                }
+            };
+
+            meta_field_defs.push(quote! {
+                #(#cfg_attrs)*
+                #field_vis #field_name: <#child_type as ::config_loadr::Load>::Meta
+            });
+            load_impl_fields.push(quote! {
+                #(#cfg_attrs)*
+                let #field_name = #load;
+            });
+            load_impl_unwraps.push(quote! {
+                #(#cfg_attrs)*
+                #field_name: #field_name?
+            });
+            meta_field_inits.push(quote! {
+                #(#cfg_attrs)*
+                #field_name: <#child_type as ::config_loadr::Load>::metadata_with_prefix(
+                    &format!("{}{}", prefix, #meta_prefix),
+                )
+            });
+            continue;
+        }
+
         // For optional fields (Option<T>), metadata should use the inner type T
         // For other fields, use the full type
         let (is_option, opt_inner_type) = extract_option_type(field_type);
@@ -102,6 +176,14 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
             field_type
         };
 
+        if is_environment_type(meta_type) {
+            let default = match &config.mode {
+                FieldMode::Default(e) => Some(e.clone()),
+                _ => None,
+            };
+            environment_fields.push((field, config.env_var.clone(), meta_type.clone(), default));
+        }
+
         // Generate metadata field for ConfigMeta struct
         meta_field_defs.push(quote! {
             #(#cfg_attrs)*
@@ -111,12 +193,45 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
         // Generate load implementation code
         let env_var = &config.env_var;
         let description = &config.description;
+        let name_str = field_name.unraw().to_string();
+        let mut options = quote! {
+            ::config_loadr::FieldOptions::new().name(#name_str)
+        };
+        if config.file_fallback || struct_config.file_fallback {
+            options = quote! { #options.file_fallback() };
+        }
+        if let Some(credential) = &config.credential {
+            options = quote! { #options.credential(#credential) };
+        }
+        if let Some(condition) = &config.required_if {
+            options = quote! { #options.required_if(#condition) };
+        }
+        for alias in &config.aliases {
+            options = quote! { #options.alias(#alias) };
+        }
+        // Secret<T> and ZeroizingSecret<T> fields are always secret
+        let secret = config.secret || is_secret_type(meta_type);
+        if secret {
+            options = quote! { #options.secret() };
+        }
+        for validator in &config.validators {
+            let constraint = &validator.constraint;
+            options = quote! { #options.constraint(#constraint) };
+        }
+
+        // Fields that aren't a single FromStr value bring their own parser
+        let parser = field_parser(&config, meta_type);
+        // List fields join arrays from a file layer with their separator
+        if let Some(separator) = parser.as_ref().and_then(|parser| parser.separator.as_ref()) {
+            options = quote! { #options.separator(#separator) };
+        }
+        let options = quote! { &#options };
 
         // For optional fields, extract the inner type from Option<T>
         let (is_option, actual_type) = extract_option_type(field_type);
 
-        let load_code = match config.mode {
-            FieldMode::Required => {
+        let load_code = match (&config.mode, parser) {
+            (FieldMode::Required, parser) => {
                 let example = config.example.as_ref().ok_or_else(|| {
                     syn::Error::new_spanned(
                         field,
@@ -124,29 +239,65 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
                     )
                 })?;
 
+                match parser {
+                    Some(FieldParser { parse, display, .. }) => quote! {
+                        #(#cfg_attrs)*
+                        let #field_name = {
+                            let example: #inner_type = #example;
+                            let display = #display;
+                            builder.required_parsed::<#inner_type>(
+                                #env_var,
+                                #description,
+                                &display(&example),
+                                #options,
+                                #parse,
+                            )
+                        };
+                    },
+                    None => quote! {
+                        #(#cfg_attrs)*
+                        let #field_name = builder.required_with::<#inner_type>(
+                            #env_var,
+                            #description,
+                            #example,
+                            #options,
+                        );
+                    },
+                }
+            }
+            (FieldMode::Default(default_expr), Some(FieldParser { parse, display, .. })) => {
                 quote! {
                     #(#cfg_attrs)*
-                    let #field_name = builder.required::<#inner_type>(
-                        #env_var,
-                        #description,
-                        #example,
-                    );
+                    let #field_name = {
+                        let default: #inner_type = #default_expr;
+                        let display = #display;
+                        let default_display = display(&default);
+                        builder.or_default_parsed::<#inner_type>(
+                            #env_var,
+                            #description,
+                            default,
+                            &default_display,
+                            #options,
+                            #parse,
+                        )
+                    };
                 }
             }
-            FieldMode::Default(ref default_expr) => {
+            (FieldMode::Default(default_expr), None) => {
                 // Skip compile-time validation - it's too restrictive
                 // Users should rely on tests instead
 
                 quote! {
                     #(#cfg_attrs)*
-                    let #field_name = builder.or_default::<#inner_type>(
+                    let #field_name = builder.or_default_with::<#inner_type>(
                         #env_var,
                         #description,
                         #default_expr,
+                        #options,
                     );
                 }
             }
-            FieldMode::Optional => {
+            (FieldMode::Optional, parser) => {
                 // For optional fields, use the inner type (without Option wrapper)
                 let opt_inner = if is_option {
                     actual_type
@@ -159,18 +310,64 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
 
                 // Example is optional - only used for documentation
                 // Type is inferred from the field's Option<T> annotation
-                quote! {
-                    #(#cfg_attrs)*
-                    let #field_name = builder.optional::<#opt_inner>(
-                        #env_var,
-                        #description,
-                        None,
-                    );
+                match parser {
+                    Some(FieldParser { parse, .. }) => quote! {
+                        #(#cfg_attrs)*
+                        let #field_name = builder.optional_parsed::<#opt_inner>(
+                            #env_var,
+                            #description,
+                            None,
+                            #options,
+                            #parse,
+                        );
+                    },
+                    None => quote! {
+                        #(#cfg_attrs)*
+                        let #field_name = builder.optional_with::<#opt_inner>(
+                            #env_var,
+                            #description,
+                            None,
+                            #options,
+                        );
+                    },
                 }
             }
+            (FieldMode::Nested { .. } | FieldMode::Repeated { .. }, _) => {
+                unreachable!("nested fields are handled above")
+            }
         };
 
-        load_impl_fields.push(load_code.clone());
+        load_impl_fields.push(load_code);
+
+        // Constraints are checked on the parsed value, a rejected value is an error
+        if !config.validators.is_empty() {
+            let checks = config.validators.iter().map(|validator| &validator.check);
+            load_impl_fields.push(quote! {
+                #(#cfg_attrs)*
+                let #field_name = builder.check(
+                    #env_var,
+                    #field_name,
+                    |value: &#meta_type| -> ::std::result::Result<(), ::std::string::String> {
+                        #(#checks?;)*
+                        Ok(())
+                    },
+                );
+            });
+        }
+
+        // Defaults used in production are reported once the environment is known
+        if struct_config
+            .environment
+            .as_ref()
+            .is_some_and(|environment| environment.unraw() == field_name.unraw())
+        {
+            load_impl_fields.push(quote! {
+                #(#cfg_attrs)*
+                if let Some(environment) = #field_name {
+                    builder.set_environment(environment);
+                }
+            });
+        }
 
         // For all fields, unwrap the Option<T> returned by builder
         let unwrap_code = if matches!(config.mode, FieldMode::Optional) {
@@ -180,10 +377,10 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
                 #field_name
             }
         } else {
-            // Required and default fields return Option<T>, unwrap them
+            // Required and default fields return None if they failed, errors are in the builder
             quote! {
                 #(#cfg_attrs)*
-                #field_name: #field_name.expect(concat!("BUG: field '", stringify!(#field_name), "' should have a value after finish()"))
+                #field_name: #field_name?
             }
         };
         load_impl_unwraps.push(unwrap_code);
@@ -195,7 +392,7 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
                 quote! {
                     #(#cfg_attrs)*
                     #field_name: ::config_loadr::ConfigFieldMeta::required(
-                        #env_var,
+                        ::config_loadr::field::prefixed_key(prefix, #env_var),
                         #description,
                         #example,
                     )
@@ -205,7 +402,7 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
                 quote! {
                     #(#cfg_attrs)*
                     #field_name: ::config_loadr::ConfigFieldMeta::optional(
-                        #env_var,
+                        ::config_loadr::field::prefixed_key(prefix, #env_var),
                         #description,
                         #default_expr,
                     )
@@ -222,24 +419,32 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
                 quote! {
                     #(#cfg_attrs)*
                     #field_name: ::config_loadr::ConfigFieldMeta::optional(
-                        #env_var,
+                        ::config_loadr::field::prefixed_key(prefix, #env_var),
                         #description,
                         #example_value,
                     )
                 }
             }
+            FieldMode::Nested { .. } | FieldMode::Repeated { .. } => {
+                unreachable!("nested fields are handled above")
+            }
+        };
+        let meta_init = if secret {
+            quote! { #meta_init.secret() }
+        } else {
+            meta_init
         };
         meta_field_inits.push(meta_init);
-
-        // Same load code for docs
-        docs_impl_fields.push(load_code);
     }
 
-    // Filter out our custom attributes (allow(missing_docs)) from struct definition
+    // Filter out our custom attributes (allow(missing_docs), config(...)) from struct definition
     let filtered_attrs: Vec<&Attribute> = struct_attrs
         .iter()
         .filter(|attr| {
             // Keep the attribute unless it's our custom ones
+            if attr.path().is_ident("config") {
+                return false;
+            }
             if attr.path().is_ident("allow") {
                 // Check if it's allow(missing_docs)
                 if let Ok(ident) = attr.parse_args::<syn::Ident>() {
@@ -252,9 +457,43 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
         })
         .collect();
 
+    // A derived Debug would print secret fields, replace it with one that redacts them
+    let mut derives_debug = false;
+    let mut struct_attrs_out = Vec::new();
+    for attr in filtered_attrs {
+        if redacts_debug && attr.path().is_ident("derive") {
+            let derives = attr.parse_args_with(
+                syn::punctuated::Punctuated::<syn::Path, Token![,]>::parse_terminated,
+            )?;
+            let (debug, rest): (Vec<_>, Vec<_>) = derives
+                .into_iter()
+                .partition(|path| path.segments.last().is_some_and(|last| last.ident == "Debug"));
+            if !debug.is_empty() {
+                derives_debug = true;
+                if !rest.is_empty() {
+                    struct_attrs_out.push(quote! { #[derive(#(#rest),*)] });
+                }
+                continue;
+            }
+        }
+        struct_attrs_out.push(quote! { #attr });
+    }
+    let debug_impl = derives_debug.then(|| {
+        let name_str = struct_name.to_string();
+        quote! {
+            impl ::std::fmt::Debug for #struct_name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    let mut debug = f.debug_struct(#name_str);
+                    #(#debug_fields)*
+                    debug.finish()
+                }
+            }
+        }
+    });
+
     // Generate the Config struct definition (with direct values)
     let struct_def = quote! {
-        #(#filtered_attrs)*
+        #(#struct_attrs_out)*
         #vis struct #struct_name {
             #(#value_field_defs),*
         }
@@ -278,43 +517,207 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
         static #meta_static_name: ::std::sync::OnceLock<#meta_struct_name> = ::std::sync::OnceLock::new();
     };
 
+    // Generate dotenv loading for new(), errors are collected with field errors
+    let dotenv_override = struct_config.dotenv_override;
+    let dotenv_load = match struct_config.dotenv.unwrap_or_default() {
+        DotenvConfig::Disabled => quote! { Ok(::config_loadr::dotenv::LoadedDotenv::default()) },
+        DotenvConfig::Files(files) => quote! {
+            ::config_loadr::dotenv::load_files(&[#(#files),*], #dotenv_override)
+        },
+        DotenvConfig::Cascade(dir) => {
+            let (_, env_var, env_type, env_default) = match environment_fields.as_slice() {
+                [field] => field,
+                [] => {
+                    return Err(syn::Error::new_spanned(
+                        input,
+                        "dotenv_cascade needs a field of type Environment",
+                    ));
+                }
+                [_, (field, ..), ..] => {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        "dotenv_cascade needs exactly one field of type Environment",
+                    ));
+                }
+            };
+            let or_default = env_default.as_ref().map(|default| {
+                quote! { .or_else(|| Some(#default)) }
+            });
+            quote! {{
+                let environment: Option<#env_type> =
+                    ::config_loadr::dotenv::cascade_value(#dir, #env_var, #dotenv_override)
+                        .and_then(|value| <#env_type as ::std::str::FromStr>::from_str(&value).ok())
+                        #or_default;
+                let environment = environment.map(|environment| environment.to_string());
+                ::config_loadr::dotenv::load_cascade(#dir, environment.as_deref(), #dotenv_override)
+            }}
+        }
+    };
+    let dotenv_fn = quote! {
+        /// Loads the configured dotenv files, collecting errors in the builder.
+        fn __config_loadr_dotenv(builder: &mut ::config_loadr::ConfigBuilder<'_>) {
+            let loaded: Result<::config_loadr::dotenv::LoadedDotenv, ::config_loadr::ConfigError> =
+                #dotenv_load;
+            let loaded = loaded.unwrap_or_else(|e| {
+                builder.push_error(e);
+                ::config_loadr::dotenv::LoadedDotenv::default()
+            });
+            builder.set_dotenv(loaded);
+        }
+    };
+
+    // Field groups are checked once every field is registered, before any field can fail
+    let mut group_checks = Vec::new();
+    for (rule, names) in &struct_config.groups {
+        for name in names {
+            if !env_field_names.contains(&name.unraw().to_string()) {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "field groups can only name fields read from a variable",
+                ));
+            }
+        }
+        let names = names.iter().map(|name| name.unraw().to_string());
+        group_checks.push(quote! {
+            builder.check_group(::config_loadr::GroupRule::#rule, &[#(#names),*]);
+        });
+    }
+
+    if let Some(environment) = struct_config
+        .environment
+        .as_ref()
+        .filter(|environment| !env_field_names.contains(&environment.unraw().to_string()))
+    {
+        return Err(syn::Error::new_spanned(
+            environment,
+            "environment must name a field read from a variable",
+        ));
+    }
+
+    // Variables with the struct prefix that no field reads are reported as warnings
+    let unknown_keys = (!struct_config.prefix.is_empty()).then(|| {
+        let prefix = &struct_config.prefix;
+        quote! { builder.warn_unknown_keys(#prefix); }
+    });
+
+    // Cross-field validation runs once every field has a value
+    let validate_code = struct_config.validate.as_ref().map(|validate| {
+        quote! {
+            if let Err(errors) = #validate(&config) {
+                builder.push_validation_errors(errors);
+                return None;
+            }
+        }
+    });
+
     // Generate Load trait implementation
     let load_impl = quote! {
         impl ::config_loadr::Load for #struct_name {
+            type Meta = #meta_struct_name;
+
             fn load() -> Self {
-                let _ = dotenvy::dotenv();
+                match <Self as ::config_loadr::Load>::new() {
+                    Ok(config) => config,
+                    Err(errors) => panic!("{}", ::config_loadr::builder::format_config_errors(&errors)),
+                }
+            }
+
+            fn new() -> Result<Self, Vec<::config_loadr::ConfigError>> {
                 let mut builder = ::config_loadr::ConfigBuilder::new();
+                Self::__config_loadr_dotenv(&mut builder);
+                Self::__config_loadr_finish(builder)
+            }
 
-                #(#load_impl_fields)*
+            fn new_with_warnings() -> Result<
+                (Self, Vec<::config_loadr::ConfigWarning>),
+                Vec<::config_loadr::ConfigError>,
+            > {
+                let mut builder = ::config_loadr::ConfigBuilder::new();
+                Self::__config_loadr_dotenv(&mut builder);
+                let (config, _, warnings) = Self::__config_loadr_finish_with_report(builder)?;
+                Ok((config, warnings))
+            }
 
-                builder.finish_or_panic();
+            fn new_strict() -> Result<Self, Vec<::config_loadr::ConfigError>> {
+                let mut builder = ::config_loadr::ConfigBuilder::new().strict();
+                Self::__config_loadr_dotenv(&mut builder);
+                Self::__config_loadr_finish(builder)
+            }
 
-                Self {
-                    #(#load_impl_unwraps),*
+            fn new_with_provenance() -> Result<
+                (Self, ::config_loadr::ProvenanceReport),
+                Vec<::config_loadr::ConfigError>,
+            > {
+                let mut builder = ::config_loadr::ConfigBuilder::new();
+                Self::__config_loadr_dotenv(&mut builder);
+                let (config, provenance, _) = Self::__config_loadr_finish_with_report(builder)?;
+                Ok((config, provenance))
+            }
+
+            fn load_from<S: ::config_loadr::Source + ?Sized>(source: &S) -> Self {
+                match <Self as ::config_loadr::Load>::new_from(source) {
+                    Ok(config) => config,
+                    Err(errors) => panic!("{}", ::config_loadr::builder::format_config_errors(&errors)),
                 }
             }
 
-            fn new() -> Result<Self, Vec<::config_loadr::ConfigError>> {
-                let _ = dotenvy::dotenv();
-                let mut builder = ::config_loadr::ConfigBuilder::new();
+            fn new_from<S: ::config_loadr::Source + ?Sized>(
+                source: &S,
+            ) -> Result<Self, Vec<::config_loadr::ConfigError>> {
+                Self::__config_loadr_finish(::config_loadr::ConfigBuilder::with_source(source))
+            }
 
-                #(#load_impl_fields)*
+            fn load_with_file(file: ::config_loadr::FileLayer) -> Self {
+                match <Self as ::config_loadr::Load>::new_with_file(file) {
+                    Ok(config) => config,
+                    Err(errors) => panic!("{}", ::config_loadr::builder::format_config_errors(&errors)),
+                }
+            }
 
-                builder.finish()?;
+            fn new_with_file(
+                file: ::config_loadr::FileLayer,
+            ) -> Result<Self, Vec<::config_loadr::ConfigError>> {
+                let mut builder = ::config_loadr::ConfigBuilder::new().with_file(file);
+                Self::__config_loadr_dotenv(&mut builder);
+                Self::__config_loadr_finish(builder)
+            }
 
-                Ok(Self {
-                    #(#load_impl_unwraps),*
-                })
+            fn load_with_builder(builder: ::config_loadr::ConfigBuilder<'_>) -> Self {
+                match <Self as ::config_loadr::Load>::new_with_builder(builder) {
+                    Ok(config) => config,
+                    Err(errors) => panic!("{}", ::config_loadr::builder::format_config_errors(&errors)),
+                }
             }
 
-            #[allow(unused_variables)]
-            fn builder_for_docs() -> ::config_loadr::ConfigBuilder {
-                let mut builder = ::config_loadr::ConfigBuilder::new();
+            fn new_with_builder(
+                builder: ::config_loadr::ConfigBuilder<'_>,
+            ) -> Result<Self, Vec<::config_loadr::ConfigError>> {
+                Self::__config_loadr_finish(builder)
+            }
 
-                #(#docs_impl_fields)*
+            fn load_into(builder: &mut ::config_loadr::ConfigBuilder<'_>) -> Option<Self> {
+                #unknown_keys
+                #(#load_impl_fields)*
+                #(#group_checks)*
+
+                let config = Self {
+                    #(#load_impl_unwraps),*
+                };
+                #validate_code
+                Some(config)
+            }
 
+            fn builder_for_docs() -> ::config_loadr::ConfigBuilder<'static> {
+                let mut builder = ::config_loadr::ConfigBuilder::for_docs();
+                let _ = <Self as ::config_loadr::Load>::load_into(&mut builder);
                 builder
             }
+
+            fn metadata_with_prefix(prefix: &str) -> #meta_struct_name {
+                #meta_struct_name {
+                    #(#meta_field_inits),*
+                }
+            }
         }
     };
 
@@ -322,6 +725,39 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
     // This allows users to call Config::load() without importing the Load trait
     let inherent_impl = quote! {
         impl #struct_name {
+            #dotenv_fn
+
+            /// Loads all fields through the builder.
+            fn __config_loadr_finish(
+                builder: ::config_loadr::ConfigBuilder<'_>,
+            ) -> Result<Self, Vec<::config_loadr::ConfigError>> {
+                let (config, _, _) = Self::__config_loadr_finish_with_report(builder)?;
+                Ok(config)
+            }
+
+            /// Loads all fields through the builder, returning the config with its
+            /// provenance and warnings.
+            fn __config_loadr_finish_with_report(
+                mut builder: ::config_loadr::ConfigBuilder<'_>,
+            ) -> Result<
+                (
+                    Self,
+                    ::config_loadr::ProvenanceReport,
+                    Vec<::config_loadr::ConfigWarning>,
+                ),
+                Vec<::config_loadr::ConfigError>,
+            > {
+                let config = <Self as ::config_loadr::Load>::load_into(&mut builder);
+                let provenance = builder.provenance();
+                let warnings = builder.finish_with_warnings()?;
+
+                Ok((
+                    config.expect("BUG: config should have a value after finish()"),
+                    provenance,
+                    warnings,
+                ))
+            }
+
             /// Loads the configuration from environment variables.
             /// Panics if any required variables are missing or invalid.
             #vis fn load() -> Self {
@@ -334,25 +770,111 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
                 <Self as ::config_loadr::Load>::new()
             }
 
+            /// Loads the configuration from environment variables, returning warnings
+            /// like deprecated variables with it.
+            /// Returns an error if any required variables are missing or invalid.
+            #vis fn new_with_warnings() -> Result<
+                (Self, Vec<::config_loadr::ConfigWarning>),
+                Vec<::config_loadr::ConfigError>,
+            > {
+                <Self as ::config_loadr::Load>::new_with_warnings()
+            }
+
+            /// Loads the configuration from environment variables, treating warnings
+            /// like deprecated variables as errors.
+            /// Returns an error if any required variables are missing or invalid.
+            #vis fn new_strict() -> Result<Self, Vec<::config_loadr::ConfigError>> {
+                <Self as ::config_loadr::Load>::new_strict()
+            }
+
+            /// Loads the configuration from environment variables, returning where
+            /// each field's value came from.
+            /// Returns an error if any required variables are missing or invalid.
+            #vis fn new_with_provenance() -> Result<
+                (Self, ::config_loadr::ProvenanceReport),
+                Vec<::config_loadr::ConfigError>,
+            > {
+                <Self as ::config_loadr::Load>::new_with_provenance()
+            }
+
+            /// Loads the configuration from the given source.
+            /// Panics if any required values are missing or invalid.
+            #vis fn load_from<S: ::config_loadr::Source + ?Sized>(source: &S) -> Self {
+                <Self as ::config_loadr::Load>::load_from(source)
+            }
+
+            /// Loads the configuration from the given source.
+            /// Returns an error if any required values are missing or invalid.
+            #vis fn new_from<S: ::config_loadr::Source + ?Sized>(
+                source: &S,
+            ) -> Result<Self, Vec<::config_loadr::ConfigError>> {
+                <Self as ::config_loadr::Load>::new_from(source)
+            }
+
+            /// Loads the configuration from environment variables with a configuration file underneath.
+            /// Panics if any required values are missing or invalid.
+            #vis fn load_with_file(file: ::config_loadr::FileLayer) -> Self {
+                <Self as ::config_loadr::Load>::load_with_file(file)
+            }
+
+            /// Loads the configuration from environment variables with a configuration file underneath.
+            /// Returns an error if any required values are missing or invalid.
+            #vis fn new_with_file(
+                file: ::config_loadr::FileLayer,
+            ) -> Result<Self, Vec<::config_loadr::ConfigError>> {
+                <Self as ::config_loadr::Load>::new_with_file(file)
+            }
+
+            /// Loads the configuration through a prepared builder.
+            /// Panics if any required values are missing or invalid.
+            #vis fn load_with_builder(builder: ::config_loadr::ConfigBuilder<'_>) -> Self {
+                <Self as ::config_loadr::Load>::load_with_builder(builder)
+            }
+
+            /// Loads the configuration through a prepared builder.
+            /// Returns an error if any required values are missing or invalid.
+            #vis fn new_with_builder(
+                builder: ::config_loadr::ConfigBuilder<'_>,
+            ) -> Result<Self, Vec<::config_loadr::ConfigError>> {
+                <Self as ::config_loadr::Load>::new_with_builder(builder)
+            }
+
+            /// Loads the configuration from an in-memory map.
+            /// Does not read the process environment or `.env` files.
+            #vis fn new_from_map(
+                map: &::std::collections::HashMap<String, String>,
+            ) -> Result<Self, Vec<::config_loadr::ConfigError>> {
+                <Self as ::config_loadr::Load>::new_from_map(map)
+            }
+
+            /// Loads the configuration from key/value pairs.
+            /// Does not read the process environment or `.env` files.
+            #vis fn from_iter<I, K, V>(iter: I) -> Result<Self, Vec<::config_loadr::ConfigError>>
+            where
+                I: IntoIterator<Item = (K, V)>,
+                K: Into<String>,
+                V: Into<String>,
+            {
+                <Self as ::config_loadr::Load>::from_iter(iter)
+            }
+
             /// Creates a builder for documentation purposes only.
             #[doc(hidden)]
-            #vis fn builder_for_docs() -> ::config_loadr::ConfigBuilder {
+            #vis fn builder_for_docs() -> ::config_loadr::ConfigBuilder<'static> {
                 <Self as ::config_loadr::Load>::builder_for_docs()
             }
 
             /// Returns a reference to the configuration metadata.
             #vis fn metadata() -> &'static #meta_struct_name {
-                #meta_static_name.get_or_init(|| {
-                    #meta_struct_name {
-                        #(#meta_field_inits),*
-                    }
-                })
+                #meta_static_name
+                    .get_or_init(|| <Self as ::config_loadr::Load>::metadata_with_prefix(""))
             }
         }
     };
 
     Ok(quote! {
         #struct_def
+        #debug_impl
         #meta_struct_def
         #meta_static
         #load_impl
@@ -360,12 +882,321 @@ fn generate_config(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream>
     })
 }
 
+/// Struct-level options from #[config(...)]
+#[derive(Debug, Default)]
+struct StructConfig {
+    file_fallback: bool,
+    dotenv: Option<DotenvConfig>,
+    dotenv_override: bool,
+    /// Prepended to every field's env var
+    prefix: String,
+    /// `fn(&Self) -> Result<(), Vec<ConfigError>>` run once every field has loaded
+    validate: Option<syn::Path>,
+    /// Field groups, as the `GroupRule` variant and the fields in the group
+    groups: Vec<(syn::Ident, Vec<syn::Ident>)>,
+    /// Field holding the `Environment`, for production checks
+    environment: Option<syn::Ident>,
+}
+
+/// Which dotenv files `new()` loads, from #[config(dotenv = ...)]
+#[derive(Debug)]
+enum DotenvConfig {
+    /// `dotenv = false`
+    Disabled,
+    /// `dotenv = ".env.local"` or `dotenv = [".env.local", ".env"]`, `.env` if unset
+    Files(Vec<String>),
+    /// `dotenv_cascade` or `dotenv_cascade = "dir"`, files chosen by the Environment field
+    Cascade(String),
+}
+
+impl Default for DotenvConfig {
+    fn default() -> Self {
+        DotenvConfig::Files(vec![".env".to_string()])
+    }
+}
+
+/// Parse #[config(file_fallback, dotenv = ..., dotenv_cascade, dotenv_override, prefix = "...",
+/// validate = path, environment = field, exactly_one_of(a, b), at_least_one_of(a, b),
+/// conflicts(a, b))] on the struct
+fn parse_struct_config(attrs: &[Attribute]) -> syn::Result<StructConfig> {
+    let mut config = StructConfig::default();
+
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("config")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("file_fallback") {
+                config.file_fallback = true;
+                Ok(())
+            } else if meta.path.is_ident("dotenv") || meta.path.is_ident("dotenv_cascade") {
+                if config.dotenv.is_some() {
+                    return Err(meta.error("dotenv and dotenv_cascade can only be set once"));
+                }
+                config.dotenv = Some(if meta.path.is_ident("dotenv") {
+                    parse_dotenv_config(&meta.value()?.parse()?)?
+                } else if meta.input.peek(Token![=]) {
+                    DotenvConfig::Cascade(meta.value()?.parse::<syn::LitStr>()?.value())
+                } else {
+                    DotenvConfig::Cascade(String::new())
+                });
+                Ok(())
+            } else if meta.path.is_ident("prefix") {
+                config.prefix = meta.value()?.parse::<syn::LitStr>()?.value();
+                Ok(())
+            } else if let Some(rule) = ["exactly_one_of", "at_least_one_of", "conflicts"]
+                .into_iter()
+                .find(|name| meta.path.is_ident(name))
+            {
+                let rule = match rule {
+                    "exactly_one_of" => "ExactlyOne",
+                    "at_least_one_of" => "AtLeastOne",
+                    _ => "AtMostOne",
+                };
+                let content;
+                syn::parenthesized!(content in meta.input);
+                let fields: Vec<syn::Ident> = content
+                    .parse_terminated(syn::Ident::parse_any, Token![,])?
+                    .into_iter()
+                    .collect();
+                if fields.len() < 2 {
+                    return Err(meta.error("field groups need at least two fields"));
+                }
+                config
+                    .groups
+                    .push((syn::Ident::new(rule, proc_macro2::Span::call_site()), fields));
+                Ok(())
+            } else if meta.path.is_ident("validate") {
+                config.validate = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("environment") {
+                config.environment = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("dotenv_override") {
+                config.dotenv_override = if meta.input.peek(Token![=]) {
+                    meta.value()?.parse::<syn::LitBool>()?.value
+                } else {
+                    true
+                };
+                Ok(())
+            } else {
+                Err(meta.error(
+                    "unknown config option, expected one of: file_fallback, dotenv, dotenv_cascade, dotenv_override, prefix, validate, environment, exactly_one_of, at_least_one_of, conflicts",
+                ))
+            }
+        })?;
+    }
+
+    Ok(config)
+}
+
+/// Parse the value of `dotenv = false | "file" | ["file", ...]`
+fn parse_dotenv_config(expr: &syn::Expr) -> syn::Result<DotenvConfig> {
+    let file = |expr: &syn::Expr| match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(s),
+            ..
+        }) => Ok(s.value()),
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "dotenv files must be string literals",
+        )),
+    };
+
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Bool(b),
+            ..
+        }) => Ok(if b.value {
+            DotenvConfig::default()
+        } else {
+            DotenvConfig::Disabled
+        }),
+        syn::Expr::Array(array) if array.elems.is_empty() => Ok(DotenvConfig::Disabled),
+        syn::Expr::Array(array) => Ok(DotenvConfig::Files(
+            array.elems.iter().map(file).collect::<syn::Result<_>>()?,
+        )),
+        _ => Ok(DotenvConfig::Files(vec![file(expr)?])),
+    }
+}
+
 #[derive(Debug)]
 struct FieldConfig {
     env_var: String,
     description: String,
     example: Option<syn::Expr>,
     mode: FieldMode,
+    file_fallback: bool,
+    /// systemd credential name, the env var name when given as a bare flag
+    credential: Option<String>,
+    /// Element separator for list fields
+    separator: Option<String>,
+    /// Pair separator for map fields
+    pair_separator: Option<String>,
+    /// Key/value separator for map fields
+    kv_separator: Option<String>,
+    /// Parse the value as JSON with serde
+    json: bool,
+    /// Custom `fn(&str) -> Result<T, E>`
+    parse_with: Option<syn::Expr>,
+    /// Custom `fn(&T) -> String` for docs
+    display_with: Option<syn::Expr>,
+    /// Constraints checked after parsing
+    validators: Vec<Validator>,
+    /// `Condition` expression for required_if / required_unless fields
+    required_if: Option<proc_macro2::TokenStream>,
+    /// Redact the value in errors, docs and provenance
+    secret: bool,
+    /// Deprecated env var names, prefixed like `env_var`
+    aliases: Vec<String>,
+}
+
+/// A constraint on a field value, like `range = 1..=65535`
+#[derive(Debug)]
+struct Validator {
+    /// `Result<(), String>` expression checking `value: &T`
+    check: proc_macro2::TokenStream,
+    /// `String` expression describing the constraint for docs
+    constraint: proc_macro2::TokenStream,
+}
+
+/// Parse the validator attributes of a field, in a fixed order
+fn parse_validators(parsed: &HashMap<String, MetaValue>) -> Vec<Validator> {
+    let mut validators = Vec::new();
+
+    if let Some(MetaValue::Expr(range)) = parsed.get("range") {
+        validators.push(Validator {
+            check: quote! { ::config_loadr::validate::range(value, &(#range)) },
+            constraint: quote! { format!("range {:?}", #range) },
+        });
+    }
+    if let Some(MetaValue::Expr(min)) = parsed.get("min_len") {
+        validators.push(Validator {
+            check: quote! { ::config_loadr::validate::min_len(value, #min) },
+            constraint: quote! { format!("min length {}", #min) },
+        });
+    }
+    if let Some(MetaValue::Expr(max)) = parsed.get("max_len") {
+        validators.push(Validator {
+            check: quote! { ::config_loadr::validate::max_len(value, #max) },
+            constraint: quote! { format!("max length {}", #max) },
+        });
+    }
+    if let Some(MetaValue::Expr(allowed)) = parsed.get("one_of") {
+        validators.push(Validator {
+            check: quote! { ::config_loadr::validate::one_of(value, &#allowed) },
+            constraint: quote! { format!("one of {:?}", #allowed) },
+        });
+    }
+    if let Some(MetaValue::Str(pattern)) = parsed.get("matches") {
+        let constraint = format!("matches `{}`", pattern);
+        validators.push(Validator {
+            check: quote! { ::config_loadr::__validate_matches!(value, #pattern) },
+            constraint: quote! { #constraint },
+        });
+    }
+
+    validators
+}
+
+/// Parser and docs renderer for a field that isn't a single FromStr value
+struct FieldParser {
+    /// `Fn(&str) -> Result<T, ParseError>`
+    parse: proc_macro2::TokenStream,
+    /// `Fn(&T) -> String`
+    display: proc_macro2::TokenStream,
+    /// Element separator, for list fields
+    separator: Option<String>,
+}
+
+/// The parser for a field, None for plain FromStr fields
+///
+/// `ty` is the field type, without `Option` for optional fields.
+fn field_parser(config: &FieldConfig, ty: &Type) -> Option<FieldParser> {
+    if config.json {
+        return Some(FieldParser {
+            parse: quote! { ::config_loadr::__parse_json!(#ty) },
+            display: quote! { ::config_loadr::__display_json!(#ty) },
+            separator: None,
+        });
+    }
+
+    if config.parse_with.is_some() || config.display_with.is_some() {
+        let parse = match &config.parse_with {
+            Some(parse_with) => quote! {
+                |value: &str| ::config_loadr::parse::with(value, #parse_with)
+            },
+            None => quote! { ::config_loadr::parse::from_str },
+        };
+        let display = match &config.display_with {
+            Some(display_with) => quote! { #display_with },
+            None => quote! { |value: &#ty| ::std::string::ToString::to_string(value) },
+        };
+        return Some(FieldParser {
+            parse,
+            display,
+            separator: None,
+        });
+    }
+
+    if config.pair_separator.is_some() || config.kv_separator.is_some() || is_map_type(ty) {
+        let pair_separator = config.pair_separator.as_deref().unwrap_or(",");
+        let kv_separator = config.kv_separator.as_deref().unwrap_or("=");
+        return Some(FieldParser {
+            parse: quote! {
+                |value: &str| ::config_loadr::parse::map(value, #pair_separator, #kv_separator)
+            },
+            display: quote! {
+                |value: &#ty| ::config_loadr::parse::display_map(value, #pair_separator, #kv_separator)
+            },
+            separator: None,
+        });
+    }
+
+    if config.separator.is_some() || is_list_type(ty) {
+        let separator = config.separator.as_deref().unwrap_or(",");
+        // Unordered sets are sorted so docs don't change between runs
+        let display = if type_name(ty).is_some_and(|name| name == "HashSet") {
+            quote! { display_set }
+        } else {
+            quote! { display_list }
+        };
+        return Some(FieldParser {
+            parse: quote! {
+                |value: &str| ::config_loadr::parse::list(value, #separator)
+            },
+            display: quote! {
+                |value: &#ty| ::config_loadr::parse::#display(value, #separator)
+            },
+            separator: Some(separator.to_string()),
+        });
+    }
+
+    None
+}
+
+/// Whether a type is a collection parsed as a list, like `Vec<T>` or `HashSet<T>`
+fn is_list_type(ty: &Type) -> bool {
+    type_name(ty).is_some_and(|name| matches!(name.as_str(), "Vec" | "HashSet" | "BTreeSet"))
+}
+
+/// Whether a type is a map parsed from `key=value` pairs, like `HashMap<K, V>`
+fn is_map_type(ty: &Type) -> bool {
+    type_name(ty).is_some_and(|name| matches!(name.as_str(), "HashMap" | "BTreeMap"))
+}
+
+/// Whether a type redacts itself, `Secret<T>` or `ZeroizingSecret<T>`
+fn is_secret_type(ty: &Type) -> bool {
+    type_name(ty).is_some_and(|name| name == "Secret" || name == "ZeroizingSecret")
+}
+
+/// The last path segment of a type, like `Vec` for `std::vec::Vec<T>`
+fn type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        _ => None,
+    }
 }
 
 #[derive(Debug)]
@@ -373,6 +1204,14 @@ enum FieldMode {
     Required,
     Default(syn::Expr),
     Optional,
+    /// A nested config struct, its keys prefixed with `prefix`
+    Nested {
+        prefix: String,
+    },
+    /// A `Vec` of nested config structs, their keys prefixed with `prefix<n>_`
+    Repeated {
+        prefix: String,
+    },
 }
 
 /// Parse #[field(env = "X", doc = "Y", default = val)] syntax
@@ -389,13 +1228,38 @@ fn parse_field_list(meta_list: &syn::MetaList) -> syn::Result<HashMap<String, Me
         if meta.input.peek(Token![=]) {
             meta.input.parse::<Token![=]>()?;
 
-            if key == "env" || key == "doc" {
+            if matches!(
+                key.as_str(),
+                "env"
+                    | "doc"
+                    | "credential"
+                    | "prefix"
+                    | "separator"
+                    | "pair_separator"
+                    | "kv_separator"
+                    | "matches"
+                    | "required_if"
+                    | "required_unless"
+            ) {
                 let value: syn::LitStr = meta.input.parse()?;
                 values.insert(key, MetaValue::Str(value.value()));
             } else {
                 let expr: syn::Expr = meta.input.parse()?;
                 values.insert(key, MetaValue::Expr(expr));
             }
+        } else if meta.input.peek(syn::token::Paren) {
+            let mut list = HashMap::new();
+            meta.parse_nested_meta(|inner| {
+                let name = inner
+                    .path
+                    .get_ident()
+                    .ok_or_else(|| inner.error("expected identifier"))?
+                    .to_string();
+                let value: syn::LitStr = inner.value()?.parse()?;
+                list.insert(name, value.value());
+                Ok(())
+            })?;
+            values.insert(key, MetaValue::List(list));
         } else {
             values.insert(key, MetaValue::Flag);
         }
@@ -406,7 +1270,45 @@ fn parse_field_list(meta_list: &syn::MetaList) -> syn::Result<HashMap<String, Me
     Ok(values)
 }
 
-fn parse_field_config(attrs: &[Attribute], allow_missing_docs: bool) -> syn::Result<FieldConfig> {
+/// Parse `required_if = "KEY"` or `required_if(env = "KEY", eq = "value")` into a
+/// `Condition` expression, the key namespaced by the struct prefix
+fn parse_condition(
+    value: &MetaValue,
+    prefix: &str,
+    field_attr: &Attribute,
+) -> syn::Result<proc_macro2::TokenStream> {
+    match value {
+        MetaValue::Str(key) => {
+            let key = format!("{}{}", prefix, key);
+            Ok(quote! { ::config_loadr::Condition::truthy(#key) })
+        }
+        MetaValue::List(list) => {
+            let key = match list.get("env") {
+                Some(key) => format!("{}{}", prefix, key),
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        field_attr,
+                        "condition must have env = \"VAR_NAME\"",
+                    ));
+                }
+            };
+            match list.get("eq") {
+                Some(eq) => Ok(quote! { ::config_loadr::Condition::equals(#key, #eq) }),
+                None => Ok(quote! { ::config_loadr::Condition::truthy(#key) }),
+            }
+        }
+        _ => Err(syn::Error::new_spanned(
+            field_attr,
+            "condition must be \"VAR_NAME\" or (env = \"VAR_NAME\", eq = \"value\")",
+        )),
+    }
+}
+
+fn parse_field_config(
+    attrs: &[Attribute],
+    allow_missing_docs: bool,
+    prefix: &str,
+) -> syn::Result<FieldConfig> {
     // Find the #[field(...)] attribute
     let field_attr = attrs.iter()
         .find(|attr| attr.path().is_ident("field"))
@@ -428,9 +1330,50 @@ fn parse_field_config(attrs: &[Attribute], allow_missing_docs: bool) -> syn::Res
         }
     };
 
-    // Extract env (required)
+    // Nested configs bring their own env, doc and mode for every field
+    if parsed.contains_key("nested") || parsed.contains_key("repeated") {
+        let nested_prefix = match parsed.get("prefix") {
+            Some(MetaValue::Str(s)) => format!("{}{}", prefix, s),
+            _ if parsed.contains_key("repeated") => {
+                return Err(syn::Error::new_spanned(
+                    field_attr,
+                    "repeated fields must have prefix = \"PREFIX_\"",
+                ));
+            }
+            _ => prefix.to_string(),
+        };
+        let mode = if parsed.contains_key("repeated") {
+            FieldMode::Repeated {
+                prefix: nested_prefix,
+            }
+        } else {
+            FieldMode::Nested {
+                prefix: nested_prefix,
+            }
+        };
+        return Ok(FieldConfig {
+            env_var: String::new(),
+            description: String::new(),
+            example: None,
+            mode,
+            file_fallback: false,
+            credential: None,
+            separator: None,
+            pair_separator: None,
+            kv_separator: None,
+            json: false,
+            parse_with: None,
+            display_with: None,
+            validators: Vec::new(),
+            required_if: None,
+            secret: false,
+            aliases: Vec::new(),
+        });
+    }
+
+    // Extract env (required), namespaced by the struct prefix
     let env_var = match parsed.get("env") {
-        Some(MetaValue::Str(s)) => s.clone(),
+        Some(MetaValue::Str(s)) => format!("{}{}", prefix, s),
         _ => {
             return Err(syn::Error::new_spanned(
                 field_attr,
@@ -468,7 +1411,10 @@ fn parse_field_config(attrs: &[Attribute], allow_missing_docs: bool) -> syn::Res
         FieldMode::Required
     } else if let Some(MetaValue::Expr(e)) = parsed.get("default") {
         FieldMode::Default(e.clone())
-    } else if parsed.contains_key("optional") {
+    } else if parsed.contains_key("optional")
+        || parsed.contains_key("required_if")
+        || parsed.contains_key("required_unless")
+    {
         FieldMode::Optional
     } else {
         return Err(syn::Error::new_spanned(
@@ -477,20 +1423,133 @@ fn parse_field_config(attrs: &[Attribute], allow_missing_docs: bool) -> syn::Res
         ));
     };
 
+    // Extract required_if / required_unless (optional), only for optional fields
+    let required_if = match (parsed.get("required_if"), parsed.get("required_unless")) {
+        (Some(_), Some(_)) => {
+            return Err(syn::Error::new_spanned(
+                field_attr,
+                "required_if and required_unless can't be combined",
+            ));
+        }
+        _ if !matches!(mode, FieldMode::Optional)
+            && (parsed.contains_key("required_if") || parsed.contains_key("required_unless")) =>
+        {
+            return Err(syn::Error::new_spanned(
+                field_attr,
+                "required_if and required_unless fields are optional and can't be required or have a default",
+            ));
+        }
+        (Some(value), None) => Some(parse_condition(value, prefix, field_attr)?),
+        (None, Some(value)) => {
+            let condition = parse_condition(value, prefix, field_attr)?;
+            Some(quote! { #condition.negate() })
+        }
+        (None, None) => None,
+    };
+
+    // Extract credential (optional), a bare flag uses the env var name
+    let credential = match parsed.get("credential") {
+        Some(MetaValue::Str(s)) => Some(s.clone()),
+        Some(_) => Some(env_var.clone()),
+        None => None,
+    };
+
+    // Extract aliases (optional), namespaced by the struct prefix like env
+    let aliases = match parsed.get("aliases") {
+        Some(MetaValue::Expr(syn::Expr::Array(array))) => array
+            .elems
+            .iter()
+            .map(|alias| match alias {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) => Ok(format!("{}{}", prefix, s.value())),
+                _ => Err(syn::Error::new_spanned(
+                    alias,
+                    "aliases must be string literals",
+                )),
+            })
+            .collect::<syn::Result<_>>()?,
+        Some(_) => {
+            return Err(syn::Error::new_spanned(
+                field_attr,
+                "aliases must be a list, like aliases = [\"OLD_NAME\"]",
+            ));
+        }
+        None => Vec::new(),
+    };
+
     Ok(FieldConfig {
         env_var,
         description,
         example,
         mode,
+        file_fallback: parsed.contains_key("file_fallback"),
+        credential,
+        aliases,
+        separator: match parsed.get("separator") {
+            Some(MetaValue::Str(s)) => Some(s.clone()),
+            _ => None,
+        },
+        pair_separator: match parsed.get("pair_separator") {
+            Some(MetaValue::Str(s)) => Some(s.clone()),
+            _ => None,
+        },
+        kv_separator: match parsed.get("kv_separator") {
+            Some(MetaValue::Str(s)) => Some(s.clone()),
+            _ => None,
+        },
+        json: parsed.contains_key("json"),
+        parse_with: match parsed.get("parse_with") {
+            Some(MetaValue::Expr(e)) => Some(e.clone()),
+            _ => None,
+        },
+        display_with: match parsed.get("display_with") {
+            Some(MetaValue::Expr(e)) => Some(e.clone()),
+            _ => None,
+        },
+        validators: parse_validators(&parsed),
+        required_if,
+        secret: parsed.contains_key("secret"),
     })
 }
 
+/// Whether a type is named Environment, like `config_loadr::Environment`
+fn is_environment_type(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Environment"),
+        _ => false,
+    }
+}
+
 /// Extract the inner type from ConfigField<T> or just return the type as-is
 fn extract_inner_type(ty: &Type) -> &Type {
     // For now, just return the type as-is since we're wrapping it ourselves
     ty
 }
 
+/// Extract the element type from Vec<T>
+fn extract_vec_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(inner_ty) => Some(inner_ty),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// Extract the inner type from Option<T>, returns (is_option, inner_type)
 fn extract_option_type(ty: &Type) -> (bool, &Type) {
     if let Type::Path(type_path) = ty {
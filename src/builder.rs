@@ -1,18 +1,55 @@
+use crate::directory::{self, DirectorySource};
+use crate::dotenv::LoadedDotenv;
+use crate::environment::Environment;
 use crate::error::ConfigError;
+use crate::field::{Condition, FieldOptions, GroupRule};
+use crate::file::{FileLayer, FileValue};
+use crate::parse::{self, ParseError};
+use crate::provenance::{FieldProvenance, Provenance, ProvenanceReport};
+use crate::resolve::SecretResolver;
+use crate::secret::{self, REDACTED};
+use crate::source::{EnvSource, Precedence, Source, SourceError};
+use crate::warning::ConfigWarning;
 use colored::Colorize;
-use std::{env, fs, path::Path, str::FromStr};
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Variable systemd sets to the directory holding a service's credentials
+pub const CREDENTIALS_DIRECTORY: &str = "CREDENTIALS_DIRECTORY";
 
 /// Metadata about a configuration field for documentation generation
 #[derive(Debug, Clone)]
 pub struct FieldMetadata {
     /// Environment variable key
     pub key: String,
+    /// Struct field name, if the field was registered by a config struct
+    pub name: Option<String>,
     /// Human-readable description
     pub description: String,
     /// Default or example value as a string
     pub default_str: String,
     /// Whether this field is required
     pub required: bool,
+    /// Whether the value can also be read from the file named by `<KEY>_FILE`
+    pub file_fallback: bool,
+    /// systemd credential name, read from `$CREDENTIALS_DIRECTORY` before the environment
+    pub credential: Option<String>,
+    /// Where the value came from, `Provenance::Missing` until it is loaded
+    pub provenance: Provenance,
+    /// The value as it was read, or the default value's string form
+    pub value: Option<String>,
+    /// Constraints on the value, like `range 1..=65535`
+    pub constraints: Vec<String>,
+    /// When an optional field is required, like `EMAIL_ENABLED is true`
+    pub condition: Option<Condition>,
+    /// Whether the value is redacted in docs, errors and provenance
+    pub secret: bool,
+    /// Deprecated keys the value is also read from
+    pub aliases: Vec<String>,
 }
 
 /// Parses an environment variable into a specific type
@@ -21,23 +58,76 @@ pub fn env_parse<'a, T: FromStr>(
     description: &str,
     example: impl Into<Option<&'a str>>,
 ) -> Result<T, ConfigError> {
-    let example = example.into();
-    match env::var(key) {
-        Ok(s) => match s.parse() {
-            Ok(parsed) => Ok(parsed),
-            Err(_) => Err(ConfigError::InvalidEnvironment {
-                key: key.to_string(),
-                value: s,
-                description: description.to_string(),
-                example: example.map(|s| s.to_string()),
-            }),
-        },
-        Err(_) => Err(ConfigError::MissingEnvVar {
+    source_parse(&EnvSource, key, description, example)
+}
+
+/// Parses a value from a [`Source`] into a specific type
+pub fn source_parse<'a, T: FromStr>(
+    source: &(impl Source + ?Sized),
+    key: &str,
+    description: &str,
+    example: impl Into<Option<&'a str>>,
+) -> Result<T, ConfigError> {
+    parse_value(source, key, description, example.into(), false)
+}
+
+/// Parses a secret environment variable into a specific type
+///
+/// Like [`env_parse`], but the raw string is dropped as soon as it's parsed,
+/// overwritten first with the `zeroize` feature, and errors are
+/// [redacted](ConfigError::redacted).
+pub fn env_parse_secret<'a, T: FromStr>(
+    key: &str,
+    description: &str,
+    example: impl Into<Option<&'a str>>,
+) -> Result<T, ConfigError> {
+    source_parse_secret(&EnvSource, key, description, example)
+}
+
+/// Parses a secret value from a [`Source`] into a specific type
+///
+/// See [`env_parse_secret`].
+pub fn source_parse_secret<'a, T: FromStr>(
+    source: &(impl Source + ?Sized),
+    key: &str,
+    description: &str,
+    example: impl Into<Option<&'a str>>,
+) -> Result<T, ConfigError> {
+    parse_value(source, key, description, example.into(), true)
+}
+
+fn parse_value<T: FromStr>(
+    source: &(impl Source + ?Sized),
+    key: &str,
+    description: &str,
+    example: Option<&str>,
+    secret: bool,
+) -> Result<T, ConfigError> {
+    let Some(value) = source.get(key) else {
+        let error = ConfigError::MissingEnvVar {
             key: key.to_string(),
             description: description.to_string(),
             example: example.map(|s| s.to_string()),
-        }),
-    }
+        };
+        return Err(if secret { error.redacted() } else { error });
+    };
+
+    let parsed = value.parse().ok();
+    let value = if secret {
+        secret::wipe(value);
+        REDACTED.to_string()
+    } else {
+        value
+    };
+    parsed.ok_or_else(|| {
+        let error = ConfigError::InvalidEnvironment {
+            key: key.to_string(),
+            value,
+            description: description.to_string(),
+            example: example.map(|s| s.to_string()),
+        };
+        if secret { error.redacted() } else { error }
+    })
 }
 
 /// Loads a required environment variable, returning an error if missing or invalid
@@ -100,8 +190,27 @@ pub fn format_config_errors(errors: &[ConfigError]) -> String {
     )
 }
 
+/// Helper to format multiple configuration warnings, like `format_config_errors`
+pub fn format_config_warnings(warnings: &[ConfigWarning]) -> String {
+    let warning_summary = warnings
+        .iter()
+        .map(|w| format!("  - {}", w))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        "Configuration loaded with {} warning(s):\n{}",
+        warnings.len().to_string().yellow().bold(),
+        warning_summary
+    )
+}
+
 /// A builder pattern for loading configuration with error collection
 ///
+/// Values are read from the process environment by default, use
+/// [`ConfigBuilder::with_source`] to read them from any other [`Source`], and
+/// [`ConfigBuilder::with_layer`] to consult more sources around it.
+///
 /// # Example
 /// ```rust
 /// use config_loadr::ConfigBuilder;
@@ -111,18 +220,437 @@ pub fn format_config_errors(errors: &[ConfigError]) -> String {
 ///
 /// builder.validate();
 /// ```
-pub struct ConfigBuilder {
+pub struct ConfigBuilder<'a> {
+    /// Every source in the order they are consulted
+    sources: Vec<Box<dyn Source + 'a>>,
+    /// Index of the source passed to `with_source` in `sources`
+    primary: usize,
+    file: Option<FileLayer>,
     errors: Vec<ConfigError>,
     fields: Vec<FieldMetadata>,
+    /// Nested configs being loaded, as (key prefix, field name) pairs
+    scopes: Vec<(String, String)>,
+    /// Field groups that were checked, as keys, for docs
+    groups: Vec<(GroupRule, Vec<String>)>,
+    /// Resolvers for secret references, by scheme
+    resolvers: Vec<(String, Box<dyn SecretResolver + 'a>)>,
+    warnings: Vec<ConfigWarning>,
+    /// Report warnings as errors
+    strict: bool,
+    /// The environment the config is loaded for, once a field has set it
+    environment: Option<Environment>,
+    /// Key prefixes whose unknown variables are reported, with open scopes applied
+    prefixes: Vec<String>,
+    /// Dotenv files loaded into the environment for this load
+    dotenv: LoadedDotenv,
+    /// Register fields for documentation instead of loading real values
+    docs: bool,
 }
 
-impl ConfigBuilder {
-    /// Create a new configuration builder
+impl ConfigBuilder<'static> {
+    /// Create a new configuration builder reading from the process environment
     pub fn new() -> Self {
+        Self::with_source(EnvSource)
+    }
+
+    /// Create a builder that registers fields for [`ConfigBuilder::write_docs`]
+    ///
+    /// Repeated groups register a single placeholder group, documented as
+    /// `WORKER_<n>_HOST`, instead of the groups currently configured.
+    pub fn for_docs() -> Self {
+        let mut builder = Self::new();
+        builder.docs = true;
+        builder
+    }
+}
+
+impl<'a> ConfigBuilder<'a> {
+    /// Create a new configuration builder reading from the given source
+    pub fn with_source(source: impl Source + 'a) -> Self {
         Self {
+            sources: vec![Box::new(source)],
+            primary: 0,
+            file: None,
             errors: Vec::new(),
             fields: Vec::new(),
+            scopes: Vec::new(),
+            groups: Vec::new(),
+            resolvers: Vec::new(),
+            warnings: Vec::new(),
+            strict: false,
+            environment: None,
+            prefixes: Vec::new(),
+            dotenv: LoadedDotenv::default(),
+            docs: false,
+        }
+    }
+
+    /// Add a configuration file underneath the source
+    ///
+    /// Values missing from the source are looked up in the file. Errors reading
+    /// the file are collected like any other configuration error.
+    pub fn with_file(mut self, file: FileLayer) -> Self {
+        if let Some(e) = file.error() {
+            self.errors.push(e.clone());
+        }
+        self.file = Some(file);
+        self
+    }
+
+    /// Collect an error found outside of field loading, like a malformed dotenv file
+    pub fn push_error(&mut self, error: ConfigError) {
+        self.errors.push(error);
+    }
+
+    /// Collect a warning, or an error in strict mode
+    ///
+    /// Warnings are ignored when registering fields for docs.
+    pub fn push_warning(&mut self, warning: ConfigWarning) {
+        if !self.docs && !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+
+    /// Report every warning as [`ConfigError::Warning`], so it fails loading
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Set the environment the config is loaded for
+    ///
+    /// In [`Environment::Prod`], every field left at its default is reported
+    /// as [`ConfigWarning::DefaultInProduction`]. `define_config!` calls this
+    /// with the field named by `#[config(environment = ...)]`, so structs only
+    /// get these warnings when they opt in.
+    pub fn set_environment(&mut self, environment: Environment) {
+        self.environment = Some(environment);
+    }
+
+    /// Record the dotenv files loaded into the environment for this load
+    ///
+    /// Values the environment got from one of the files are reported with the
+    /// file and line in [`ConfigBuilder::provenance`]. `define_config!` calls
+    /// this with the files `new()` loads.
+    pub fn set_dotenv(&mut self, dotenv: LoadedDotenv) {
+        self.dotenv = dotenv;
+    }
+
+    /// Report variables starting with `prefix` that no field reads
+    ///
+    /// Checked once loading finishes, against every key of the sources, so a
+    /// misspelled `BILLING_PROT` is reported as [`ConfigWarning::UnknownKey`].
+    /// `define_config!` calls this for structs with `#[config(prefix = ...)]`.
+    pub fn warn_unknown_keys(&mut self, prefix: &str) {
+        let prefix = self.scoped_key(prefix);
+        if !self.docs && !self.prefixes.contains(&prefix) {
+            self.prefixes.push(prefix);
+        }
+    }
+
+    /// Collect the errors of a config's cross-field validation
+    ///
+    /// Inside a nested config the errors are reported under its path, like
+    /// `db / ...`, so they can be told apart from the parent's. An empty list
+    /// still fails the load, reported as a generic [`ConfigError::Inconsistent`].
+    pub fn push_validation_errors(&mut self, mut errors: Vec<ConfigError>) {
+        if errors.is_empty() {
+            errors.push(ConfigError::Inconsistent {
+                keys: Vec::new(),
+                reason: "validation failed without reporting an error".to_string(),
+            });
+        }
+        for error in errors {
+            self.push_scoped_error(error);
+        }
+    }
+
+    /// Check how many fields of a group are set
+    ///
+    /// `names` are struct field names, in the current scope. A field counts
+    /// as set when its value came from anywhere but its default, including a
+    /// value that failed to parse. The group is listed in the generated docs.
+    pub fn check_group(&mut self, rule: GroupRule, names: &[&str]) {
+        let names: Vec<String> = names.iter().map(|name| self.scoped_name(name)).collect();
+        let fields: Vec<&FieldMetadata> = names
+            .iter()
+            .filter_map(|name| {
+                self.fields
+                    .iter()
+                    .rev()
+                    .find(|field| field.name.as_ref() == Some(name))
+            })
+            .collect();
+        let group: Vec<String> = fields.iter().map(|field| field.key.clone()).collect();
+        let set: Vec<String> = fields
+            .iter()
+            .filter(|field| !matches!(field.provenance, Provenance::Missing | Provenance::Default))
+            .map(|field| field.key.clone())
+            .collect();
+
+        let error = match rule {
+            GroupRule::ExactlyOne | GroupRule::AtLeastOne if set.is_empty() => {
+                Some(ConfigError::MissingOneOf {
+                    group: group.clone(),
+                })
+            }
+            GroupRule::ExactlyOne | GroupRule::AtMostOne if set.len() > 1 => {
+                Some(ConfigError::ConflictingFields {
+                    set,
+                    group: group.clone(),
+                })
+            }
+            _ => None,
+        };
+        // Docs mode has no values to check
+        if let Some(error) = error.filter(|_| !self.docs) {
+            self.push_scoped_error(error);
+        }
+        if !self.groups.contains(&(rule, group.clone())) {
+            self.groups.push((rule, group));
+        }
+    }
+
+    /// Collect an error about the config being loaded rather than one of its fields
+    fn push_scoped_error(&mut self, error: ConfigError) {
+        let path = self
+            .scopes
+            .iter()
+            .map(|(_, name)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(".");
+        let error = if path.is_empty() {
+            error
+        } else {
+            ConfigError::Nested {
+                path,
+                error: Box::new(error),
+            }
+        };
+        self.errors.push(error);
+    }
+
+    /// Start loading a nested config
+    ///
+    /// Until the matching [`ConfigBuilder::pop_scope`], every key is prefixed
+    /// with `prefix` and every field name with `name.`, so a field `url` with
+    /// key `URL` in scope `("DB_", "db")` is read from `DB_URL` and reported
+    /// as `db.url`. Scopes nest.
+    pub fn push_scope(&mut self, prefix: &str, name: &str) {
+        self.scopes.push((prefix.to_string(), name.to_string()));
+    }
+
+    /// Finish loading the nested config started by the last [`ConfigBuilder::push_scope`]
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Load a group of nested configs numbered `<prefix>0_`, `<prefix>1_`, ...
+    ///
+    /// Indices are discovered from the keys of every source and must be
+    /// contiguous from 0, a gap is collected as an error. Each group is loaded
+    /// by `load` in its own scope, see [`ConfigBuilder::push_scope`], so errors
+    /// name the full key like `WORKER_1_PORT`. Returns None if any group failed.
+    pub fn repeated<T>(
+        &mut self,
+        prefix: &str,
+        name: &str,
+        mut load: impl FnMut(&mut Self) -> Option<T>,
+    ) -> Option<Vec<T>> {
+        if self.docs {
+            self.push_scope(&format!("{}<n>_", prefix), &format!("{}.<n>", name));
+            let _ = load(self);
+            self.pop_scope();
+            return Some(Vec::new());
+        }
+
+        let indices = self.group_indices(&self.scoped_key(prefix));
+        let count = (0..).take_while(|n| indices.contains(n)).count();
+        if let Some(&found) = indices.range(count..).next() {
+            self.errors.push(ConfigError::GroupIndexGap {
+                prefix: self.scoped_key(prefix),
+                missing: count,
+                found,
+            });
+        }
+
+        let mut groups = Some(Vec::with_capacity(count));
+        for n in 0..count {
+            self.push_scope(&format!("{}{}_", prefix, n), &format!("{}.{}", name, n));
+            let group = load(self);
+            self.pop_scope();
+            match (&mut groups, group) {
+                (Some(groups), Some(group)) => groups.push(group),
+                _ => groups = None,
+            }
+        }
+        groups
+    }
+
+    /// Indices `n` of every key in the sources starting with `<prefix><n>_`
+    fn group_indices(&self, prefix: &str) -> BTreeSet<usize> {
+        self.sources
+            .iter()
+            .flat_map(|source| source.keys())
+            .filter_map(|key| {
+                let (index, _) = key.strip_prefix(prefix)?.split_once('_')?;
+                // Only canonical numbers, so WORKER_01_ isn't read as group 1
+                let n: usize = index.parse().ok()?;
+                (n.to_string() == index).then_some(n)
+            })
+            .collect()
+    }
+
+    /// A key with the prefixes of every open scope
+    fn scoped_key(&self, key: &str) -> String {
+        self.scopes
+            .iter()
+            .map(|(prefix, _)| prefix.as_str())
+            .chain([key])
+            .collect()
+    }
+
+    /// A field name as a dotted path through every open scope
+    fn scoped_name(&self, name: &str) -> String {
+        self.scopes
+            .iter()
+            .map(|(_, scope)| scope.as_str())
+            .chain([name])
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Collect an error for the most recently registered field, wrapped with
+    /// its path when it belongs to a nested config and redacted when it's secret
+    fn push_field_error(&mut self, error: ConfigError) {
+        let error = match self.fields.last() {
+            Some(field) if field.secret => error.redacted(),
+            _ => error,
+        };
+        let error = match self.fields.last() {
+            Some(field) if !self.scopes.is_empty() => ConfigError::Nested {
+                path: field.name.clone().unwrap_or_else(|| field.key.clone()),
+                error: Box::new(error),
+            },
+            _ => error,
+        };
+        self.errors.push(error);
+    }
+
+    /// Consult another source before or after the primary source
+    ///
+    /// Override layers added later win over those added earlier, fallback
+    /// layers added later are consulted last. The file layer is always
+    /// consulted after every source.
+    pub fn with_layer(mut self, source: impl Source + 'a, precedence: Precedence) -> Self {
+        match precedence {
+            Precedence::Override => {
+                self.sources.insert(0, Box::new(source));
+                self.primary += 1;
+            }
+            Precedence::Fallback => self.sources.push(Box::new(source)),
         }
+        self
+    }
+
+    /// Resolve values like `scheme://reference` with `resolver` before parsing them
+    ///
+    /// Applies to values from every source and file. Registering a scheme
+    /// again replaces its resolver. Values whose reference was resolved are
+    /// reported by their reference in errors and provenance, never by the secret.
+    ///
+    /// # Example
+    /// ```rust
+    /// use config_loadr::{ConfigBuilder, FileResolver};
+    ///
+    /// // DB_PASSWORD=file:///run/secrets/db_password
+    /// let builder = ConfigBuilder::new().with_resolver("file", FileResolver);
+    /// ```
+    pub fn with_resolver(
+        mut self,
+        scheme: impl Into<String>,
+        resolver: impl SecretResolver + 'a,
+    ) -> Self {
+        let scheme = scheme.into();
+        self.resolvers
+            .retain(|(registered, _)| *registered != scheme);
+        self.resolvers.push((scheme, Box::new(resolver)));
+        self
+    }
+
+    /// Resolve a value that is a reference for a registered scheme, returning
+    /// the secret and the reference, or the value unchanged
+    fn resolve(
+        &self,
+        key: &str,
+        description: &str,
+        value: String,
+    ) -> Result<(String, Option<String>), ConfigError> {
+        let resolver = value.split_once("://").and_then(|(scheme, _)| {
+            self.resolvers
+                .iter()
+                .find(|(registered, _)| registered == scheme)
+        });
+        let Some((scheme, resolver)) = resolver else {
+            return Ok((value, None));
+        };
+
+        match resolver.resolve(&value[scheme.len() + 3..]) {
+            Ok(secret) => Ok((secret, Some(value))),
+            Err(message) => Err(ConfigError::UnresolvedSecret {
+                key: key.to_string(),
+                reference: value,
+                message,
+                description: description.to_string(),
+            }),
+        }
+    }
+
+    /// The primary source this builder reads values from
+    pub fn source(&self) -> &dyn Source {
+        &*self.sources[self.primary]
+    }
+
+    /// Look up a raw value in every source in precedence order
+    fn get_raw(&self, key: &str) -> Result<Option<(String, Provenance)>, SourceError> {
+        for source in &self.sources {
+            if let Some(value) = source.try_get(key)? {
+                let provenance = match (source.provenance(key), self.dotenv.origin(key)) {
+                    (Provenance::Env, Some(origin)) => Provenance::Dotenv {
+                        path: origin.path.clone(),
+                        line: origin.line,
+                    },
+                    (provenance, _) => provenance,
+                };
+                return Ok(Some((value, provenance)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The systemd credentials directory, if the sources have one
+    fn credentials_directory(&self) -> Result<Option<PathBuf>, SourceError> {
+        Ok(self
+            .get_raw(CREDENTIALS_DIRECTORY)?
+            .map(|(dir, _)| PathBuf::from(dir)))
+    }
+
+    /// Read a systemd credential, returning Ok(None) if there is no credentials
+    /// directory or the credential isn't in it
+    fn credential(&self, name: &str) -> Result<Option<(String, Provenance)>, SourceError> {
+        let Some(dir) = self.credentials_directory()? else {
+            return Ok(None);
+        };
+
+        let credentials = DirectorySource::new(dir);
+        Ok(credentials.try_get(name)?.map(|value| {
+            (
+                value,
+                Provenance::Credential {
+                    path: credentials.path().join(name),
+                },
+            )
+        }))
     }
 
     /// Load a required field, collecting errors if it fails
@@ -132,21 +660,7 @@ impl ConfigBuilder {
         description: &'static str,
         example: T,
     ) -> Option<T> {
-        // Capture metadata
-        self.fields.push(FieldMetadata {
-            key: key.to_string(),
-            description: description.to_string(),
-            default_str: example.to_string(),
-            required: true,
-        });
-
-        match env_required(key, description, example) {
-            Ok(value) => Some(value),
-            Err(e) => {
-                self.errors.push(e);
-                None
-            }
-        }
+        self.required_with(key, description, example, &FieldOptions::default())
     }
 
     /// Load a field, fallback to default value if missing
@@ -158,21 +672,7 @@ impl ConfigBuilder {
         description: &'static str,
         default: T,
     ) -> Option<T> {
-        // Capture metadata
-        self.fields.push(FieldMetadata {
-            key: key.to_string(),
-            description: description.to_string(),
-            default_str: default.to_string(),
-            required: false,
-        });
-
-        match env_or_default(key, description, default) {
-            Ok(value) => Some(value),
-            Err(e) => {
-                self.errors.push(e);
-                None
-            }
-        }
+        self.or_default_with(key, description, default, &FieldOptions::default())
     }
 
     /// Load an optional field that may be None
@@ -185,23 +685,604 @@ impl ConfigBuilder {
         description: &'static str,
         example: impl Into<Option<&'static str>>,
     ) -> Option<T> {
-        let example_str = example.into();
+        self.optional_with(key, description, example, &FieldOptions::default())
+    }
+
+    /// Load a required field with extra options, see [`ConfigBuilder::required`]
+    pub fn required_with<T: FromStr + std::fmt::Display + Clone>(
+        &mut self,
+        key: &str,
+        description: &str,
+        example: T,
+        options: &FieldOptions,
+    ) -> Option<T> {
+        let example = example.to_string();
+        self.required_parsed(key, description, &example, options, parse::from_str)
+    }
+
+    /// Load a field with a default and extra options, see [`ConfigBuilder::or_default`]
+    pub fn or_default_with<T: FromStr + std::fmt::Display + Clone>(
+        &mut self,
+        key: &str,
+        description: &str,
+        default: T,
+        options: &FieldOptions,
+    ) -> Option<T> {
+        let default_str = default.to_string();
+        self.or_default_parsed(
+            key,
+            description,
+            default,
+            &default_str,
+            options,
+            parse::from_str,
+        )
+    }
+
+    /// Load an optional field with extra options, see [`ConfigBuilder::optional`]
+    pub fn optional_with<'e, T: FromStr>(
+        &mut self,
+        key: &str,
+        description: &str,
+        example: impl Into<Option<&'e str>>,
+        options: &FieldOptions,
+    ) -> Option<T> {
+        self.optional_parsed(key, description, example.into(), options, parse::from_str)
+    }
 
-        // Capture metadata
+    /// Load a required field with a custom parser
+    ///
+    /// `example` is the example as it should appear in errors and docs. See
+    /// [`crate::parse`] for parsers of lists and other values.
+    pub fn required_parsed<T>(
+        &mut self,
+        key: &str,
+        description: &str,
+        example: &str,
+        options: &FieldOptions,
+        parse: impl FnOnce(&str) -> Result<T, ParseError>,
+    ) -> Option<T> {
+        let key = &self.scoped_key(key);
+        self.push_field(key, description, example, true, options);
+
+        match self.load(key, description, Some(example), options, parse) {
+            Ok(Some(value)) => Some(value),
+            Ok(None) => {
+                let error = match options.credential {
+                    Some(name) => ConfigError::MissingCredential {
+                        key: key.to_string(),
+                        name: name.to_string(),
+                        directory: self.credentials_directory().ok().flatten(),
+                        description: description.to_string(),
+                        example: Some(example.to_string()),
+                    },
+                    None => ConfigError::MissingEnvVar {
+                        key: key.to_string(),
+                        description: description.to_string(),
+                        example: Some(example.to_string()),
+                    },
+                };
+                self.push_field_error(error);
+                None
+            }
+            Err(e) => {
+                self.push_field_error(e);
+                None
+            }
+        }
+    }
+
+    /// Load a field with a default and a custom parser
+    ///
+    /// `default_display` is the default as it should appear in docs and provenance.
+    pub fn or_default_parsed<T>(
+        &mut self,
+        key: &str,
+        description: &str,
+        default: T,
+        default_display: &str,
+        options: &FieldOptions,
+        parse: impl FnOnce(&str) -> Result<T, ParseError>,
+    ) -> Option<T> {
+        let key = &self.scoped_key(key);
+        self.push_field(key, description, default_display, false, options);
+
+        match self.load(key, description, Some(default_display), options, parse) {
+            Ok(Some(value)) => Some(value),
+            Ok(None) => {
+                self.set_provenance(Provenance::Default, Some(default_display.to_string()));
+                Some(default)
+            }
+            Err(e) => {
+                self.push_field_error(e);
+                None
+            }
+        }
+    }
+
+    /// Load an optional field with a custom parser
+    pub fn optional_parsed<T>(
+        &mut self,
+        key: &str,
+        description: &str,
+        example: Option<&str>,
+        options: &FieldOptions,
+        parse: impl FnOnce(&str) -> Result<T, ParseError>,
+    ) -> Option<T> {
+        let key = &self.scoped_key(key);
+        self.push_field(key, description, example.unwrap_or(""), false, options);
+
+        match self.load(key, description, example, options, parse) {
+            Ok(Some(value)) => Some(value),
+            Ok(None) => {
+                if let Some(condition) = &options.required_if {
+                    let condition = self.scoped_condition(condition);
+                    let value = self.condition_value(&condition.key);
+                    if condition.holds(value.as_deref()) {
+                        self.push_field_error(ConfigError::MissingConditional {
+                            key: key.to_string(),
+                            description: description.to_string(),
+                            example: example.map(str::to_string),
+                            condition: condition.to_string(),
+                        });
+                    }
+                }
+                None
+            }
+            Err(e) => {
+                self.push_field_error(e);
+                None
+            }
+        }
+    }
+
+    fn push_field(
+        &mut self,
+        key: &str,
+        description: &str,
+        default_str: &str,
+        required: bool,
+        options: &FieldOptions,
+    ) {
         self.fields.push(FieldMetadata {
             key: key.to_string(),
+            name: options.name.map(|name| self.scoped_name(name)),
             description: description.to_string(),
-            default_str: example_str.unwrap_or("").to_string(),
-            required: false,
+            default_str: if options.secret && !default_str.is_empty() {
+                REDACTED.to_string()
+            } else {
+                default_str.to_string()
+            },
+            required,
+            file_fallback: options.file_fallback,
+            credential: options.credential.map(str::to_string),
+            provenance: Provenance::Missing,
+            value: None,
+            constraints: options.constraints.clone(),
+            condition: options
+                .required_if
+                .as_ref()
+                .map(|condition| self.scoped_condition(condition)),
+            secret: options.secret,
+            aliases: options
+                .aliases
+                .iter()
+                .map(|alias| self.scoped_key(alias))
+                .collect(),
         });
+    }
 
-        match env_or_option(key, description, example_str) {
-            Ok(value) => value,
-            Err(e) => {
-                self.errors.push(e);
-                None
+    /// The value a condition on `key` is checked against
+    ///
+    /// When a field with that key was loaded before, this is the value it
+    /// loaded, wherever it came from, including its default. Otherwise, and
+    /// for secret fields whose value isn't kept, the sources are read directly.
+    fn condition_value(&self, key: &str) -> Option<String> {
+        match self.fields.iter().rev().find(|field| field.key == key) {
+            Some(field) if !field.secret => match field.provenance {
+                Provenance::Missing => None,
+                _ => field.value.clone(),
+            },
+            _ => self.get_raw(key).ok().flatten().map(|(raw, _)| raw),
+        }
+    }
+
+    /// A condition with its key read in the current scope
+    fn scoped_condition(&self, condition: &Condition) -> Condition {
+        Condition {
+            key: self.scoped_key(&condition.key),
+            ..condition.clone()
+        }
+    }
+
+    /// Check a loaded value against the constraints on its field
+    ///
+    /// `check` returns the reason the value is rejected, see [`crate::validate`]
+    /// for the common ones. A rejected value is reported as
+    /// [`ConfigError::ValidationFailed`] and None is returned, like a value
+    /// that failed to parse. A missing value (None) is not checked.
+    ///
+    /// # Example
+    /// ```rust
+    /// use config_loadr::{ConfigBuilder, FieldOptions, validate};
+    ///
+    /// let mut builder = ConfigBuilder::new();
+    /// let options = FieldOptions::new().constraint("range 1..=65535");
+    /// let port = builder.or_default_with("PORT", "Server port", 8080u16, &options);
+    /// let port = builder.check("PORT", port, |port| validate::range(port, &(1..=65535)));
+    /// ```
+    pub fn check<T>(
+        &mut self,
+        key: &str,
+        value: Option<T>,
+        check: impl FnOnce(&T) -> Result<(), String>,
+    ) -> Option<T> {
+        let reason = match check(value.as_ref()?) {
+            Ok(()) => return value,
+            Err(reason) => reason,
+        };
+
+        let key = self.scoped_key(key);
+        let field = self.fields.iter().rev().find(|field| field.key == key);
+        let error = ConfigError::ValidationFailed {
+            value: field.and_then(|f| f.value.clone()).unwrap_or_default(),
+            description: field.map(|f| f.description.clone()).unwrap_or_default(),
+            key,
+            reason,
+        };
+        self.push_field_error(error);
+        None
+    }
+
+    /// Record where the value of the most recently registered field came from
+    fn set_provenance(&mut self, provenance: Provenance, value: Option<String>) {
+        if let Some(field) = self.fields.last_mut() {
+            field.provenance = provenance;
+            field.value = if field.secret {
+                value.map(|_| REDACTED.to_string())
+            } else {
+                value
+            };
+        }
+    }
+
+    /// Look up and parse a value, returning Ok(None) if it is missing everywhere
+    fn load<T>(
+        &mut self,
+        key: &str,
+        description: &str,
+        example: Option<&str>,
+        options: &FieldOptions,
+        parse: impl FnOnce(&str) -> Result<T, ParseError>,
+    ) -> Result<Option<T>, ConfigError> {
+        let Some((value, provenance)) = self.lookup(key, description, example, options)? else {
+            return Ok(None);
+        };
+        // Secret values are only kept for as long as it takes to parse them
+        let recorded = if options.secret {
+            REDACTED.to_string()
+        } else {
+            value.clone()
+        };
+        self.set_provenance(provenance.clone(), Some(recorded));
+
+        // Resolved values are treated the same, and shown as their reference
+        let (value, reference) = self.resolve(key, description, value)?;
+        let shown = if options.secret {
+            Some(REDACTED.to_string())
+        } else {
+            reference
+        };
+
+        let parsed = parse(&value);
+        // The parser's reason may quote the resolved value as well
+        let redact_reason = shown.is_some();
+        let value = match shown {
+            Some(shown) => {
+                if options.secret {
+                    secret::wipe(value);
+                }
+                shown
             }
+            None => value,
+        };
+        let error = match parsed {
+            Ok(parsed) => return Ok(Some(parsed)),
+            Err(e) => e,
+        };
+        if let Some(reason) = error.reason() {
+            return Err(ConfigError::InvalidValue {
+                key: key.to_string(),
+                value,
+                reason: if redact_reason {
+                    REDACTED.to_string()
+                } else {
+                    reason.to_string()
+                },
+                description: description.to_string(),
+                example: example.map(str::to_string),
+            });
         }
+
+        Err(match provenance {
+            Provenance::File {
+                path,
+                key: file_key,
+            } => ConfigError::InvalidFileValue {
+                path,
+                key: file_key,
+                value,
+                description: description.to_string(),
+                example: example.map(str::to_string),
+            },
+            _ => ConfigError::InvalidEnvironment {
+                key: key.to_string(),
+                value,
+                description: description.to_string(),
+                example: example.map(str::to_string),
+            },
+        })
+    }
+
+    /// Look up a key in the sources, then each of its deprecated aliases
+    ///
+    /// Every alias that is set is reported as a deprecation warning. Names set to
+    /// different values are an error, whichever would have been used.
+    fn get_aliased(
+        &mut self,
+        key: &str,
+        description: &str,
+        options: &FieldOptions,
+        unreadable: impl Fn(&str, SourceError) -> ConfigError,
+    ) -> Result<Option<(String, Provenance)>, ConfigError> {
+        let mut found = self.get_raw(key).map_err(|e| unreadable(key, e))?;
+        let mut set = Vec::from_iter(found.as_ref().map(|_| key.to_string()));
+        let mut conflict = false;
+
+        for alias in &options.aliases {
+            let alias = self.scoped_key(alias);
+            let Some((value, provenance)) =
+                self.get_raw(&alias).map_err(|e| unreadable(&alias, e))?
+            else {
+                continue;
+            };
+            self.push_warning(ConfigWarning::DeprecatedAlias {
+                alias: alias.clone(),
+                key: key.to_string(),
+            });
+
+            set.push(alias.clone());
+            match &found {
+                Some((existing, _)) => {
+                    conflict |= *existing != value;
+                    // Only compared, a secret is dropped like the used value would be
+                    if options.secret {
+                        secret::wipe(value);
+                    }
+                }
+                None => {
+                    found = Some((
+                        value,
+                        Provenance::Alias {
+                            alias,
+                            provenance: Box::new(provenance),
+                        },
+                    ))
+                }
+            }
+        }
+
+        if conflict {
+            if let Some((value, _)) = found.filter(|_| options.secret) {
+                secret::wipe(value);
+            }
+            return Err(ConfigError::ConflictingAliases {
+                key: key.to_string(),
+                set,
+                description: description.to_string(),
+            });
+        }
+        Ok(found)
+    }
+
+    /// Find the raw value for a field in its systemd credential if it has one,
+    /// then in the sources, then in a `KEY_FILE` file if enabled, then in the
+    /// file layer
+    fn lookup(
+        &mut self,
+        key: &str,
+        description: &str,
+        example: Option<&str>,
+        options: &FieldOptions,
+    ) -> Result<Option<(String, Provenance)>, ConfigError> {
+        let unreadable = |key: &str, e: SourceError| ConfigError::UnreadableFile {
+            key: key.to_string(),
+            path: e.path,
+            message: e.message,
+            description: description.to_string(),
+        };
+
+        if let Some(name) = options.credential {
+            if let Some(found) = self.credential(name).map_err(|e| unreadable(key, e))? {
+                return Ok(Some(found));
+            }
+        }
+
+        if let Some(found) = self.get_aliased(key, description, options, unreadable)? {
+            return Ok(Some(found));
+        }
+
+        if options.file_fallback {
+            let file_key = format!("{}_FILE", key);
+            if let Some((path, _)) = self
+                .get_raw(&file_key)
+                .map_err(|e| unreadable(&file_key, e))?
+            {
+                let path = PathBuf::from(path);
+                return match directory::read_trimmed(&path) {
+                    Ok(value) => Ok(Some((
+                        value,
+                        Provenance::EnvFile {
+                            key: file_key,
+                            path,
+                        },
+                    ))),
+                    Err(e) => Err(unreadable(
+                        &file_key,
+                        SourceError {
+                            path,
+                            message: e.to_string(),
+                        },
+                    )),
+                };
+            }
+        }
+
+        let Some(file) = &self.file else {
+            return Ok(None);
+        };
+
+        // The field being looked up is always the most recently registered one
+        let name = self.fields.last().and_then(|field| field.name.as_deref());
+        match file.lookup(name, key) {
+            Some((file_key, FileValue::Scalar(value))) => Ok(Some((
+                value.clone(),
+                Provenance::File {
+                    path: file.path().to_path_buf(),
+                    key: file_key.to_string(),
+                },
+            ))),
+            // Arrays are joined for list fields, unless an element would be split
+            Some((file_key, FileValue::List(items))) => match options
+                .separator
+                .filter(|separator| !items.iter().any(|item| item.contains(separator)))
+            {
+                Some(separator) => Ok(Some((
+                    items.join(separator),
+                    Provenance::File {
+                        path: file.path().to_path_buf(),
+                        key: file_key.to_string(),
+                    },
+                ))),
+                None => Err(ConfigError::InvalidFileValue {
+                    path: file.path().to_path_buf(),
+                    key: file_key.to_string(),
+                    value: "<array>".to_string(),
+                    description: description.to_string(),
+                    example: example.map(str::to_string),
+                }),
+            },
+            Some((file_key, FileValue::Unsupported(kind))) => Err(ConfigError::InvalidFileValue {
+                path: file.path().to_path_buf(),
+                key: file_key.to_string(),
+                value: format!("<{}>", kind),
+                description: description.to_string(),
+                example: example.map(str::to_string),
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Fields registered with this builder so far, in registration order
+    pub fn fields(&self) -> &[FieldMetadata] {
+        &self.fields
+    }
+
+    /// Where every registered field's value came from, and the dotenv files read
+    pub fn provenance(&self) -> ProvenanceReport {
+        ProvenanceReport::new(
+            self.fields
+                .iter()
+                .map(|field| FieldProvenance {
+                    key: field.key.clone(),
+                    name: field.name.clone(),
+                    value: field.value.clone(),
+                    provenance: field.provenance.clone(),
+                })
+                .collect(),
+        )
+        .with_dotenv_files(self.dotenv.files().to_vec())
+    }
+
+    /// Errors for keys in a strict file layer that no registered field uses
+    fn unknown_file_keys(&self) -> Vec<ConfigError> {
+        match &self.file {
+            Some(file) if file.is_strict() => file.unknown_keys(|file_key| {
+                self.fields
+                    .iter()
+                    .any(|field| field.key == file_key || field.name.as_deref() == Some(file_key))
+            }),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Every variable starting with a watched prefix that no field reads
+    fn unknown_keys(&self) -> Vec<ConfigWarning> {
+        if self.prefixes.is_empty() {
+            return Vec::new();
+        }
+
+        let known = |key: &str| {
+            self.fields.iter().any(|field| {
+                field.key == key
+                    || field.aliases.iter().any(|alias| alias == key)
+                    || field
+                        .condition
+                        .as_ref()
+                        .is_some_and(|condition| condition.key == key)
+                    || (field.file_fallback && key.strip_suffix("_FILE") == Some(&field.key))
+            })
+        };
+        let keys: BTreeSet<String> = self
+            .sources
+            .iter()
+            .flat_map(|source| source.keys())
+            .collect();
+        keys.into_iter()
+            .filter(|key| !known(key))
+            .filter_map(|key| {
+                let prefix = self
+                    .prefixes
+                    .iter()
+                    .filter(|prefix| key.starts_with(prefix.as_str()))
+                    .max_by_key(|prefix| prefix.len())?
+                    .clone();
+                Some(ConfigWarning::UnknownKey { key, prefix })
+            })
+            .collect()
+    }
+
+    /// Every warning, including those only known once every field has loaded
+    pub fn warnings(&self) -> Vec<ConfigWarning> {
+        let mut warnings = self.warnings.clone();
+        if self
+            .environment
+            .is_some_and(|environment| environment.is_prod())
+        {
+            warnings.extend(
+                self.fields
+                    .iter()
+                    .filter(|field| field.provenance == Provenance::Default)
+                    .map(|field| ConfigWarning::DefaultInProduction {
+                        key: field.key.clone(),
+                        default: field.default_str.clone(),
+                        description: field.description.clone(),
+                    }),
+            );
+        }
+        warnings.extend(self.unknown_keys());
+        warnings
+    }
+
+    /// Errors for the warnings in strict mode, none otherwise
+    fn strict_errors(&self) -> Vec<ConfigError> {
+        if !self.strict {
+            return Vec::new();
+        }
+        self.warnings()
+            .into_iter()
+            .map(|warning| ConfigError::Warning { warning })
+            .collect()
     }
 
     /// Validate that all configuration fields loaded successfully
@@ -209,29 +1290,48 @@ impl ConfigBuilder {
     /// Unlike `finish()`, this doesn't consume the builder, allowing you to call
     /// `write_docs()` afterward.
     pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
-        if self.errors.is_empty() {
+        let mut errors = self.errors.clone();
+        errors.extend(self.unknown_file_keys());
+        errors.extend(self.strict_errors());
+
+        if errors.is_empty() {
             Ok(())
         } else {
-            Err(self.errors.clone())
+            Err(errors)
         }
     }
 
     /// Finish building and return any errors that were collected
     ///
-    /// This method consumes the builder. Use `validate()` if you need to keep
-    /// the builder for calling `write_docs()`.
+    /// Warnings aren't reported, use `finish_with_warnings()` to get them. This
+    /// method consumes the builder. Use `validate()` if you need to keep the
+    /// builder for calling `write_docs()`.
     pub fn finish(self) -> Result<(), Vec<ConfigError>> {
-        if self.errors.is_empty() {
-            Ok(())
-        } else {
+        self.finish_with_warnings().map(|_| ())
+    }
+
+    /// Finish building and return the warnings, or any errors that were collected
+    ///
+    /// In strict mode the warnings are returned as errors instead.
+    pub fn finish_with_warnings(mut self) -> Result<Vec<ConfigWarning>, Vec<ConfigError>> {
+        let unknown = self.unknown_file_keys();
+        self.errors.extend(unknown);
+        let strict = self.strict_errors();
+        self.errors.extend(strict);
+
+        if !self.errors.is_empty() {
             Err(self.errors)
+        } else if self.strict {
+            Ok(Vec::new())
+        } else {
+            Ok(self.warnings())
         }
     }
 
     /// Finish building and panic if there were any errors
     pub fn finish_or_panic(self) {
-        if !self.errors.is_empty() {
-            panic!("{}", format_config_errors(&self.errors));
+        if let Err(errors) = self.finish() {
+            panic!("{}", format_config_errors(&errors));
         }
     }
 
@@ -253,28 +1353,104 @@ impl ConfigBuilder {
     pub fn write_docs(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
         let mut md = String::new();
 
+        // Nested configs add a column with each field's dotted path
+        let nested = self
+            .fields
+            .iter()
+            .any(|field| field.name.as_deref().is_some_and(|name| name.contains('.')));
+
+        // Validated fields add a column listing their constraints
+        let constrained = self
+            .fields
+            .iter()
+            .any(|field| !field.constraints.is_empty());
+
         // Summary table
         md.push_str("## Environment Variables Summary\n\n");
-        md.push_str("| Variable | Required | Description | Default/Example |\n");
-        md.push_str("|----------|----------|-------------|------------------|\n");
+        if nested {
+            md.push_str("| Field ");
+        }
+        md.push_str("| Variable | Required | Description | Default/Example |");
+        if constrained {
+            md.push_str(" Constraints |");
+        }
+        md.push('\n');
+        if nested {
+            md.push_str("|-------");
+        }
+        md.push_str("|----------|----------|-------------|------------------|");
+        if constrained {
+            md.push_str("-------------|");
+        }
+        md.push('\n');
         for field in &self.fields {
-            let required_str = if field.required { "Yes" } else { "No" };
+            if nested {
+                md.push_str(&format!("| {} ", field.name.as_deref().unwrap_or("-")));
+            }
+            let mut key_display = if field.file_fallback {
+                format!("{} (or {}_FILE)", field.key, field.key)
+            } else {
+                field.key.clone()
+            };
+            if let Some(name) = &field.credential {
+                key_display = format!("${}/{}, then {}", CREDENTIALS_DIRECTORY, name, key_display);
+            }
+            let required_str = match &field.condition {
+                Some(condition) => format!("If {}", condition),
+                None if field.required => "Yes".to_string(),
+                None => "No".to_string(),
+            };
             let default_display = if field.default_str.is_empty() {
                 "-".to_string()
             } else {
                 field.default_str.clone()
             };
             md.push_str(&format!(
-                "| {} | {} | {} | {} |\n",
-                field.key, required_str, field.description, default_display
+                "| {} | {} | {} | {} |",
+                key_display, required_str, field.description, default_display
             ));
+            if constrained {
+                let constraints = if field.constraints.is_empty() {
+                    "-".to_string()
+                } else {
+                    field.constraints.join(", ").replace('|', "\\|")
+                };
+                md.push_str(&format!(" {} |", constraints));
+            }
+            md.push('\n');
+        }
+
+        if self.fields.iter().any(|field| field.credential.is_some()) {
+            md.push_str(&format!(
+                "\nWhen a variable lists several places they are checked in order. \
+                 Credentials are read from `${}`, set by systemd for services using \
+                 `LoadCredential=`, and the environment variable is used when the \
+                 credential is not there.\n",
+                CREDENTIALS_DIRECTORY
+            ));
+        }
+
+        if self.fields.iter().any(|field| !field.aliases.is_empty()) {
+            md.push_str("\n## Deprecated Variables\n\n");
+            for field in &self.fields {
+                for alias in &field.aliases {
+                    md.push_str(&format!("- `{}`: use `{}` instead\n", alias, field.key));
+                }
+            }
+        }
+
+        if !self.groups.is_empty() {
+            md.push_str("\n## Field Groups\n\n");
+            for (rule, keys) in &self.groups {
+                md.push_str(&format!("- {}\n", rule.describe(keys)));
+            }
         }
 
         fs::write(path, md)
     }
 }
 
-impl Default for ConfigBuilder {
+impl Default for ConfigBuilder<'static> {
     fn default() -> Self {
         Self::new()
     }
@@ -284,6 +1460,7 @@ impl Default for ConfigBuilder {
 mod tests {
     use super::*;
     use crate::field::ConfigFieldMeta;
+    use crate::test_util::PairSource;
 
     #[test]
     fn test_builder_new() {
@@ -476,4 +1653,107 @@ mod tests {
         // Builder still accessible here
         assert_eq!(builder.fields.len(), 0);
     }
+
+    #[test]
+    fn test_builder_reads_from_custom_source() {
+        let source = PairSource(&[("SOURCE_PORT", "9090"), ("SOURCE_BAD", "nope")]);
+        let mut builder = ConfigBuilder::with_source(&source);
+
+        assert_eq!(builder.required("SOURCE_PORT", "Port", 8080u16), Some(9090));
+        assert_eq!(
+            builder.or_default("SOURCE_MISSING", "Missing", 1u8),
+            Some(1)
+        );
+        assert_eq!(builder.optional::<u16>("SOURCE_BAD", "Bad", None), None);
+
+        let errors = builder.finish().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            ConfigError::InvalidEnvironment { key, .. } if key == "SOURCE_BAD"
+        ));
+    }
+
+    #[test]
+    fn test_source_parse_secret_redacts_errors() {
+        let source = PairSource(&[("SECRET_PIN", "1234"), ("SECRET_BAD", "hunter2")]);
+
+        let pin: u16 = source_parse_secret(&source, "SECRET_PIN", "PIN", None).unwrap();
+        assert_eq!(pin, 1234);
+        assert!(matches!(
+            source_parse_secret::<u16>(&source, "SECRET_BAD", "PIN", "1234"),
+            Err(ConfigError::InvalidEnvironment { value, example, .. })
+                if value == REDACTED && example.as_deref() == Some(REDACTED)
+        ));
+        assert!(matches!(
+            source_parse::<u16>(&source, "SECRET_BAD", "PIN", None),
+            Err(ConfigError::InvalidEnvironment { value, .. }) if value == "hunter2"
+        ));
+    }
+
+    #[test]
+    fn test_builder_layers_in_precedence_order() {
+        let primary = PairSource(&[("LAYER_A", "primary"), ("LAYER_B", "primary")]);
+        let over = PairSource(&[("LAYER_A", "override")]);
+        let fallback = PairSource(&[("LAYER_B", "fallback"), ("LAYER_C", "fallback")]);
+        let mut builder = ConfigBuilder::with_source(&primary)
+            .with_layer(&fallback, Precedence::Fallback)
+            .with_layer(&over, Precedence::Override);
+
+        assert_eq!(
+            builder.required("LAYER_A", "A", String::new()).as_deref(),
+            Some("override")
+        );
+        assert_eq!(
+            builder.required("LAYER_B", "B", String::new()).as_deref(),
+            Some("primary")
+        );
+        assert_eq!(
+            builder.required("LAYER_C", "C", String::new()).as_deref(),
+            Some("fallback")
+        );
+        assert_eq!(builder.source().get("LAYER_A").as_deref(), Some("primary"));
+    }
+
+    #[test]
+    fn test_builder_records_provenance() {
+        let source = PairSource(&[("PROV_PORT", "9090")]);
+        let file = FileLayer::from_pairs("app.conf", [("host", "example.com")]);
+        let mut builder = ConfigBuilder::with_source(&source).with_file(file);
+
+        let _ = builder.required::<u16>("PROV_PORT", "Port", 8080);
+        let _ = builder.or_default_with(
+            "PROV_HOST",
+            "Host",
+            "localhost".to_string(),
+            &FieldOptions::new().name("host"),
+        );
+        let _ = builder.or_default("PROV_DEBUG", "Debug", false);
+        let _ = builder.optional::<u32>("PROV_WORKERS", "Workers", None);
+
+        let report = builder.provenance();
+        assert_eq!(
+            report.get("PROV_PORT").unwrap().provenance,
+            Provenance::Source
+        );
+        assert_eq!(
+            report.get("host").unwrap().provenance,
+            Provenance::File {
+                path: "app.conf".into(),
+                key: "host".to_string()
+            }
+        );
+        assert_eq!(
+            report.get("PROV_DEBUG").unwrap().value.as_deref(),
+            Some("false")
+        );
+        assert_eq!(
+            report.get("PROV_DEBUG").unwrap().provenance,
+            Provenance::Default
+        );
+        assert_eq!(
+            report.get("PROV_WORKERS").unwrap().provenance,
+            Provenance::Missing
+        );
+    }
 }
@@ -0,0 +1,193 @@
+use crate::{
+    provenance::Provenance,
+    source::{Source, SourceError},
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Read a file holding a single value, without surrounding whitespace
+///
+/// The content is trimmed in place, so the value doesn't leave a second copy
+/// in memory for the loader to wipe.
+pub(crate) fn read_trimmed(path: &Path) -> io::Result<String> {
+    let mut content = fs::read_to_string(path)?;
+    content.truncate(content.trim_end().len());
+    let start = content.len() - content.trim_start().len();
+    content.drain(..start);
+    Ok(content)
+}
+
+/// Reads values from a directory holding one file per key
+///
+/// This is the layout written by Kubernetes ConfigMap and Secret volume
+/// mounts and by Vault agent templates: the file name is the key and the
+/// file content, trimmed, is the value.
+///
+/// Symlinks are followed, so the Kubernetes layout where every key links
+/// into a `..data` directory works as-is. Entries starting with a dot, like
+/// `..data` and the timestamped directories it points to, are never treated
+/// as keys. A directory that doesn't exist has no keys.
+///
+/// # Example
+/// ```rust
+/// use config_loadr::{ConfigBuilder, DirectorySource, Precedence};
+///
+/// // Environment variables win, the mounted secrets fill in the rest
+/// let builder = ConfigBuilder::new()
+///     .with_layer(DirectorySource::new("/etc/secrets"), Precedence::Fallback);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DirectorySource {
+    path: PathBuf,
+}
+
+impl DirectorySource {
+    /// Create a source reading files from the given directory
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The directory values are read from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of the file holding a key, or None if the key can't be a file name
+    fn file_for(&self, key: &str) -> Option<PathBuf> {
+        let valid = !key.is_empty() && !key.starts_with('.') && !key.contains(['/', '\\']);
+        valid.then(|| self.path.join(key))
+    }
+}
+
+impl Source for DirectorySource {
+    fn get(&self, key: &str) -> Option<String> {
+        self.try_get(key).ok().flatten()
+    }
+
+    fn try_get(&self, key: &str) -> Result<Option<String>, SourceError> {
+        let Some(path) = self.file_for(key) else {
+            return Ok(None);
+        };
+
+        // Follows symlinks, a dangling link is reported as missing
+        match fs::metadata(&path) {
+            Ok(metadata) if metadata.is_file() => {}
+            Ok(_) => return Ok(None),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(SourceError {
+                    path,
+                    message: e.to_string(),
+                });
+            }
+        }
+
+        match read_trimmed(&path) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) => Err(SourceError {
+                path,
+                message: e.to_string(),
+            }),
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.path) else {
+            return Vec::new();
+        };
+
+        let mut keys: Vec<String> = entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| !name.starts_with('.'))
+            .filter(|name| self.path.join(name).is_file())
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    fn provenance(&self, key: &str) -> Provenance {
+        Provenance::Directory {
+            path: self.path.join(key),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_reads_one_file_per_key() {
+        let dir = temp_dir("directory-plain", &[("PORT", b"8080\n"), (".hidden", b"x")]);
+        fs::create_dir(dir.join("nested")).unwrap();
+
+        let source = DirectorySource::new(&dir);
+        assert_eq!(source.get("PORT"), Some("8080".to_string()));
+        assert_eq!(source.get("HOST"), None);
+        assert_eq!(source.get(".hidden"), None);
+        assert_eq!(source.get("nested"), None);
+        assert_eq!(source.get("../PORT"), None);
+        assert_eq!(source.keys(), vec!["PORT".to_string()]);
+        assert_eq!(
+            source.provenance("PORT"),
+            Provenance::Directory {
+                path: dir.join("PORT")
+            }
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_trimmed_strips_both_ends() {
+        let dir = temp_dir("directory-trimmed", &[("TOKEN", b" \t s3cret \r\n")]);
+
+        let value = read_trimmed(&dir.join("TOKEN")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(value, "s3cret");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_follows_kubernetes_data_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let dir = temp_dir("directory-kubernetes", &[]);
+        let revision = dir.join("..2024_01_01_00_00_00.000000000");
+        fs::create_dir(&revision).unwrap();
+        fs::write(revision.join("DB_PASSWORD"), "hunter2").unwrap();
+        symlink("..2024_01_01_00_00_00.000000000", dir.join("..data")).unwrap();
+        symlink("..data/DB_PASSWORD", dir.join("DB_PASSWORD")).unwrap();
+        symlink("..data/REMOVED", dir.join("REMOVED")).unwrap();
+
+        let source = DirectorySource::new(&dir);
+        assert_eq!(source.get("DB_PASSWORD"), Some("hunter2".to_string()));
+        assert_eq!(source.try_get("REMOVED"), Ok(None));
+        assert_eq!(source.keys(), vec!["DB_PASSWORD".to_string()]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unreadable_file_names_path() {
+        let dir = temp_dir("directory-unreadable", &[("BINARY", &[0xff, 0xfe, 0x00])]);
+
+        let source = DirectorySource::new(&dir);
+        let error = source.try_get("BINARY").unwrap_err();
+        assert_eq!(error.path, dir.join("BINARY"));
+        assert_eq!(source.get("BINARY"), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_directory_has_no_keys() {
+        let source = DirectorySource::new("/nonexistent/config-loadr/directory");
+        assert!(source.keys().is_empty());
+        assert_eq!(source.try_get("PORT"), Ok(None));
+    }
+}
@@ -0,0 +1,412 @@
+use crate::error::ConfigError;
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Where a variable loaded from a dotenv file was defined
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DotenvOrigin {
+    /// Path of the dotenv file
+    pub path: PathBuf,
+    /// Line number (1-based) of the definition
+    pub line: usize,
+}
+
+/// The dotenv files a load read, and the variables they set
+///
+/// Pass it to [`ConfigBuilder::set_dotenv`](crate::ConfigBuilder::set_dotenv)
+/// so provenance points at the file and line of each variable. Only keys are
+/// kept, never values, so secrets read from a dotenv file aren't copied.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadedDotenv {
+    files: Vec<PathBuf>,
+    origins: BTreeMap<String, DotenvOrigin>,
+}
+
+impl LoadedDotenv {
+    /// The files that were read, in load order
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// The file and line a variable was set from, None if no file set it
+    pub fn origin(&self, key: &str) -> Option<&DotenvOrigin> {
+        self.origins.get(key)
+    }
+
+    fn extend(&mut self, other: LoadedDotenv) {
+        self.files.extend(other.files);
+        self.origins.extend(other.origins);
+    }
+}
+
+/// Load the first `.env` file found in the current directory or its parents
+///
+/// Variables that are already set in the environment are kept.
+pub fn dotenv() -> Result<LoadedDotenv, ConfigError> {
+    load_files(&[".env"], false)
+}
+
+/// Load several dotenv files in order, skipping files that don't exist
+///
+/// Relative paths are searched for in the current directory and its parents.
+/// Without `override_existing`, variables that are already set are kept, so
+/// the environment wins over every file and earlier files win over later
+/// ones. With `override_existing`, every file overrides what was set before
+/// it, so later files win.
+///
+/// Every file is parsed before any is loaded, so a malformed file leaves the
+/// environment untouched.
+///
+/// Returns the files that were loaded, in load order, and the variables they set.
+pub fn load_files(
+    files: &[impl AsRef<Path>],
+    override_existing: bool,
+) -> Result<LoadedDotenv, ConfigError> {
+    let parsed = files
+        .iter()
+        .filter_map(|file| locate(file.as_ref()))
+        .map(|path| parse_file(&path).map(|entries| (path, entries)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut loaded = LoadedDotenv::default();
+    for (path, entries) in parsed {
+        loaded.extend(apply(&path, entries, override_existing));
+    }
+    Ok(loaded)
+}
+
+/// Load the dotenv files for an environment, most specific first
+///
+/// The chain is `.env.{environment}.local`, `.env.{environment}`,
+/// `.env.local` and `.env` in `dir`, where files earlier in the chain win.
+/// Without an environment only `.env.local` and `.env` are loaded. Files are
+/// found like in [`load_files`] and missing files are skipped.
+///
+/// Returns the files that were loaded, in load order, and the variables they set.
+pub fn load_cascade(
+    dir: impl AsRef<Path>,
+    environment: Option<&str>,
+    override_existing: bool,
+) -> Result<LoadedDotenv, ConfigError> {
+    let dir = dir.as_ref();
+    let mut files = Vec::new();
+    if let Some(environment) = environment {
+        files.push(dir.join(format!(".env.{}.local", environment)));
+        files.push(dir.join(format!(".env.{}", environment)));
+    }
+    files.push(dir.join(".env.local"));
+    files.push(dir.join(".env"));
+
+    // Overriding files win when loaded last
+    if override_existing {
+        files.reverse();
+    }
+    load_files(&files, override_existing)
+}
+
+/// The value of `key` that chooses the environment of a cascade in `dir`
+///
+/// The base files `.env.local` and `.env` are read without loading them, so
+/// an environment named in `.env` selects its files like one set in the
+/// process environment. The process environment wins over the files unless
+/// `override_existing` is true, matching what [`load_cascade`] loads.
+pub fn cascade_value(
+    dir: impl AsRef<Path>,
+    key: &str,
+    override_existing: bool,
+) -> Option<String> {
+    let dir = dir.as_ref();
+    let from_files = [".env.local", ".env"]
+        .into_iter()
+        .find_map(|file| read_value(&dir.join(file), key, override_existing));
+    let from_env = env::var(key).ok();
+    if override_existing {
+        from_files.or(from_env)
+    } else {
+        from_env.or(from_files)
+    }
+}
+
+/// Load a dotenv file into the process environment
+///
+/// Variables that are already set are kept unless `override_existing` is true.
+/// The whole file is parsed first, so a malformed file sets nothing.
+/// Returns the file and the line of every variable that was set.
+pub fn load_file(
+    path: impl AsRef<Path>,
+    override_existing: bool,
+) -> Result<LoadedDotenv, ConfigError> {
+    let path = path.as_ref();
+    let entries = parse_file(path)?;
+    Ok(apply(path, entries, override_existing))
+}
+
+/// A parsed dotenv file, its content and every assignment in file order
+struct ParsedDotenv {
+    content: String,
+    entries: Vec<(String, String)>,
+}
+
+/// Parse a whole dotenv file without touching the environment
+fn parse_file(path: &Path) -> Result<ParsedDotenv, ConfigError> {
+    let mut content = fs::read_to_string(path).map_err(|e| file_error(path, e))?;
+    if content.starts_with('\u{feff}') {
+        content.remove(0);
+    }
+
+    let entries = dotenvy::from_read_iter(content.as_bytes())
+        .map(|item| item.map_err(|e| file_error(path, e)))
+        .collect::<Result<_, _>>()?;
+    Ok(ParsedDotenv { content, entries })
+}
+
+/// Set the variables of a parsed file, returning the line of each one set
+fn apply(path: &Path, parsed: ParsedDotenv, override_existing: bool) -> LoadedDotenv {
+    let mut loaded = LoadedDotenv {
+        files: vec![path.to_path_buf()],
+        origins: BTreeMap::new(),
+    };
+    for (key, value) in parsed.entries {
+        if !override_existing && env::var(&key).is_ok() {
+            continue;
+        }
+
+        env::set_var(&key, &value);
+        let line = line_of(&parsed.content, &key, override_existing).unwrap_or(0);
+        loaded.origins.insert(
+            key,
+            DotenvOrigin {
+                path: path.to_path_buf(),
+                line,
+            },
+        );
+    }
+    loaded
+}
+
+fn file_error(path: &Path, error: impl ToString) -> ConfigError {
+    ConfigError::FileError {
+        path: path.to_path_buf(),
+        message: error.to_string(),
+    }
+}
+
+/// The value a dotenv file assigns to `key`, the last assignment if `last`
+///
+/// Missing and malformed files have no value, loading them reports the error.
+fn read_value(file: &Path, key: &str, last: bool) -> Option<String> {
+    let content = fs::read_to_string(locate(file)?).ok()?;
+    let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+    let mut values = dotenvy::from_read_iter(content.as_bytes())
+        .map_while(Result::ok)
+        .filter(|(k, _)| k == key)
+        .map(|(_, value)| value);
+    if last { values.last() } else { values.next() }
+}
+
+/// The path of a dotenv file, relative paths searched for like in [`load_files`]
+fn locate(file: &Path) -> Option<PathBuf> {
+    if file.is_absolute() {
+        file.is_file().then(|| file.to_path_buf())
+    } else {
+        find(file)
+    }
+}
+
+/// Search the current directory and its parents for a file
+fn find(filename: &Path) -> Option<PathBuf> {
+    let mut dir = env::current_dir().ok()?;
+    loop {
+        let candidate = dir.join(filename);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        if !dir.pop() {
+            return None;
+        }
+    }
+}
+
+/// Line number (1-based) where `key` is assigned, the last assignment if `last`
+fn line_of(content: &str, key: &str, last: bool) -> Option<usize> {
+    let mut lines = content.lines().enumerate().filter(|(_, line)| {
+        let line = line.trim_start();
+        let line = line
+            .strip_prefix("export ")
+            .map(str::trim_start)
+            .unwrap_or(line);
+        line.strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    });
+
+    let found = if last { lines.last() } else { lines.next() };
+    found.map(|(index, _)| index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_line_of() {
+        let content = "# comment\nPORT=1\nexport HOST = a\nPORT=2\nPORTS=3\n";
+
+        assert_eq!(line_of(content, "PORT", false), Some(2));
+        assert_eq!(line_of(content, "PORT", true), Some(4));
+        assert_eq!(line_of(content, "HOST", false), Some(3));
+        assert_eq!(line_of(content, "MISSING", false), None);
+    }
+
+    #[test]
+    fn test_load_file_records_origin() {
+        let dir = temp_dir(
+            "dotenv",
+            &[(".env", b"# test\nDOTENV_ORIGIN_TEST=loaded\n")],
+        );
+        let path = dir.join(".env");
+
+        let loaded = load_file(&path, false).unwrap();
+
+        assert_eq!(env::var("DOTENV_ORIGIN_TEST").unwrap(), "loaded");
+        assert_eq!(loaded.files(), std::slice::from_ref(&path));
+        let found = loaded.origin("DOTENV_ORIGIN_TEST").unwrap();
+        assert_eq!(found.path, path);
+        assert_eq!(found.line, 2);
+
+        // Variables that were already set aren't recorded
+        let loaded = load_file(&path, false).unwrap();
+        assert_eq!(loaded.origin("DOTENV_ORIGIN_TEST"), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_files_order() {
+        let dir = temp_dir(
+            "dotenv-order",
+            &[
+                (
+                    ".env.first",
+                    b"DOTENV_ORDER_KEPT=first\nDOTENV_ORDER_OVERRIDDEN=first\n",
+                ),
+                (
+                    ".env.second",
+                    b"DOTENV_ORDER_KEPT=second\nDOTENV_ORDER_OVERRIDDEN=second\n",
+                ),
+            ],
+        );
+        let first = dir.join(".env.first");
+        let second = dir.join(".env.second");
+        let missing = dir.join(".env.missing");
+
+        let loaded = load_files(&[&first, &missing, &second], false).unwrap();
+        assert_eq!(loaded.files(), [first.clone(), second.clone()]);
+        assert_eq!(env::var("DOTENV_ORDER_KEPT").unwrap(), "first");
+        assert_eq!(loaded.origin("DOTENV_ORDER_KEPT").unwrap().path, first);
+
+        env::remove_var("DOTENV_ORDER_OVERRIDDEN");
+        let loaded = load_files(&[&first, &second], true).unwrap();
+        assert_eq!(env::var("DOTENV_ORDER_OVERRIDDEN").unwrap(), "second");
+        assert_eq!(loaded.origin("DOTENV_ORDER_OVERRIDDEN").unwrap().path, second);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_cascade_order() {
+        let dir = temp_dir(
+            "cascade",
+            &[
+                (".env", b"DOTENV_CASCADE_A=base\nDOTENV_CASCADE_B=base\n"),
+                (".env.local", b"DOTENV_CASCADE_A=local\n"),
+                (".env.dev", b"DOTENV_CASCADE_A=dev\nDOTENV_CASCADE_B=dev\n"),
+            ],
+        );
+
+        let loaded = load_cascade(&dir, Some("dev"), false).unwrap();
+        assert_eq!(
+            loaded.files(),
+            [
+                dir.join(".env.dev"),
+                dir.join(".env.local"),
+                dir.join(".env")
+            ]
+        );
+        assert_eq!(env::var("DOTENV_CASCADE_A").unwrap(), "dev");
+        assert_eq!(env::var("DOTENV_CASCADE_B").unwrap(), "dev");
+
+        let loaded = load_cascade(&dir, None, true).unwrap();
+        assert_eq!(loaded.files(), [dir.join(".env"), dir.join(".env.local")]);
+        assert_eq!(env::var("DOTENV_CASCADE_A").unwrap(), "local");
+        assert_eq!(env::var("DOTENV_CASCADE_B").unwrap(), "base");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cascade_value_reads_base_files() {
+        let dir = temp_dir(
+            "cascade-value",
+            &[
+                (".env", b"DOTENV_CASCADE_VALUE=base\nDOTENV_CASCADE_BASE=base\n"),
+                (".env.local", b"DOTENV_CASCADE_VALUE=local\n"),
+            ],
+        );
+
+        assert_eq!(
+            cascade_value(&dir, "DOTENV_CASCADE_VALUE", false).as_deref(),
+            Some("local")
+        );
+        assert_eq!(
+            cascade_value(&dir, "DOTENV_CASCADE_BASE", false).as_deref(),
+            Some("base")
+        );
+        assert_eq!(cascade_value(&dir, "DOTENV_CASCADE_UNSET", false), None);
+
+        // The process environment wins unless the files override it
+        env::set_var("DOTENV_CASCADE_BASE", "env");
+        assert_eq!(
+            cascade_value(&dir, "DOTENV_CASCADE_BASE", false).as_deref(),
+            Some("env")
+        );
+        assert_eq!(
+            cascade_value(&dir, "DOTENV_CASCADE_BASE", true).as_deref(),
+            Some("base")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_file_parse_error() {
+        let dir = temp_dir("dotenv-bad", &[(".env", b"DOTENV_BAD_GOOD=1\nBAD LINE\n")]);
+        let path = dir.join(".env");
+
+        let result = load_file(&path, false);
+        assert!(matches!(result, Err(ConfigError::FileError { .. })));
+        assert!(env::var("DOTENV_BAD_GOOD").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_files_parses_every_file_first() {
+        let dir = temp_dir(
+            "dotenv-bad-second",
+            &[
+                (".env.good", b"DOTENV_BEFORE_BAD=1\n"),
+                (".env.bad", b"BAD LINE\n"),
+            ],
+        );
+
+        let result = load_files(&[dir.join(".env.good"), dir.join(".env.bad")], false);
+        assert!(matches!(result, Err(ConfigError::FileError { .. })));
+        assert!(env::var("DOTENV_BEFORE_BAD").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,5 +1,7 @@
+use crate::secret::REDACTED;
+use crate::warning::ConfigWarning;
 use colored::Colorize;
-use std::fmt;
+use std::{fmt, path::PathBuf};
 
 /// Errors that can occur during configuration loading
 #[derive(Debug, Clone)]
@@ -10,6 +12,15 @@ pub enum ConfigError {
         description: String,
         example: Option<String>,
     },
+    /// A field that is only required under a condition is missing while the
+    /// condition holds
+    MissingConditional {
+        key: String,
+        description: String,
+        example: Option<String>,
+        /// Why the variable is required, like `EMAIL_ENABLED is true`
+        condition: String,
+    },
     /// An environment variable has an invalid value
     InvalidEnvironment {
         key: String,
@@ -17,6 +28,209 @@ pub enum ConfigError {
         description: String,
         example: Option<String>,
     },
+    /// A value could not be parsed, with the parser's explanation
+    InvalidValue {
+        key: String,
+        value: String,
+        /// Why the value is invalid, like which list element failed
+        reason: String,
+        description: String,
+        example: Option<String>,
+    },
+    /// A value was parsed but doesn't satisfy a constraint on the field
+    ValidationFailed {
+        key: String,
+        value: String,
+        /// The constraint that failed, like `must be in range 1..=65535`
+        reason: String,
+        description: String,
+    },
+    /// Values of several fields don't fit together, reported by cross-field validation
+    Inconsistent {
+        /// Every key involved, like `["TLS_CERT", "TLS_KEY"]`
+        keys: Vec<String>,
+        reason: String,
+    },
+    /// More than one field of a group is set where only one is allowed
+    ConflictingFields {
+        /// The keys that are set
+        set: Vec<String>,
+        /// Every key of the group
+        group: Vec<String>,
+    },
+    /// No field of a group is set where at least one is required
+    MissingOneOf {
+        /// Every key of the group
+        group: Vec<String>,
+    },
+    /// A key and its deprecated aliases are set to different values
+    ConflictingAliases {
+        key: String,
+        /// Every name that is set, the key first, then aliases in order
+        set: Vec<String>,
+        description: String,
+    },
+    /// A configuration file could not be read or parsed
+    FileError { path: PathBuf, message: String },
+    /// A value in a configuration file has the wrong type or can't be parsed
+    InvalidFileValue {
+        path: PathBuf,
+        /// Key in the file
+        key: String,
+        value: String,
+        description: String,
+        example: Option<String>,
+    },
+    /// A file referenced for a value exists in configuration but can't be read
+    UnreadableFile {
+        /// The key that referenced the file
+        key: String,
+        path: PathBuf,
+        message: String,
+        description: String,
+    },
+    /// A secret reference could not be resolved by its resolver
+    UnresolvedSecret {
+        key: String,
+        /// The reference as it was set, like `secretref://prod/db#password`
+        reference: String,
+        /// Why the resolver failed
+        message: String,
+        description: String,
+    },
+    /// A required field read from a systemd credential is missing from both
+    /// the credentials directory and the environment
+    MissingCredential {
+        key: String,
+        /// Credential name
+        name: String,
+        /// Value of `$CREDENTIALS_DIRECTORY`, None if it is not set
+        directory: Option<PathBuf>,
+        description: String,
+        example: Option<String>,
+    },
+    /// A key in a strict configuration file doesn't match any field
+    UnknownFileKey { path: PathBuf, key: String },
+    /// Repeated groups are not numbered contiguously from 0
+    GroupIndexGap {
+        /// Prefix of the group, like `WORKER_`
+        prefix: String,
+        /// The first index with no keys
+        missing: usize,
+        /// A higher index that has keys
+        found: usize,
+    },
+    /// A warning reported as an error by a builder in strict mode
+    Warning { warning: ConfigWarning },
+    /// An error in a field of a nested config
+    Nested {
+        /// Dotted path of the field, like `db.url`
+        path: String,
+        error: Box<ConfigError>,
+    },
+}
+
+impl ConfigError {
+    /// The same error with the value, example and parse reason replaced by `[REDACTED]`
+    ///
+    /// Used for errors of secret fields, so they can be logged.
+    pub fn redacted(self) -> Self {
+        let redact = |text: String| {
+            if text.is_empty() {
+                text
+            } else {
+                REDACTED.to_string()
+            }
+        };
+        match self {
+            ConfigError::MissingEnvVar {
+                key,
+                description,
+                example,
+            } => ConfigError::MissingEnvVar {
+                key,
+                description,
+                example: example.map(redact),
+            },
+            ConfigError::MissingConditional {
+                key,
+                description,
+                example,
+                condition,
+            } => ConfigError::MissingConditional {
+                key,
+                description,
+                example: example.map(redact),
+                condition,
+            },
+            ConfigError::InvalidEnvironment {
+                key,
+                value,
+                description,
+                example,
+            } => ConfigError::InvalidEnvironment {
+                key,
+                value: redact(value),
+                description,
+                example: example.map(redact),
+            },
+            ConfigError::InvalidValue {
+                key,
+                value,
+                reason,
+                description,
+                example,
+            } => ConfigError::InvalidValue {
+                key,
+                value: redact(value),
+                reason: redact(reason),
+                description,
+                example: example.map(redact),
+            },
+            ConfigError::ValidationFailed {
+                key,
+                value,
+                reason,
+                description,
+            } => ConfigError::ValidationFailed {
+                key,
+                value: redact(value),
+                reason,
+                description,
+            },
+            ConfigError::InvalidFileValue {
+                path,
+                key,
+                value,
+                description,
+                example,
+            } => ConfigError::InvalidFileValue {
+                path,
+                key,
+                value: redact(value),
+                description,
+                example: example.map(redact),
+            },
+            ConfigError::MissingCredential {
+                key,
+                name,
+                directory,
+                description,
+                example,
+            } => ConfigError::MissingCredential {
+                key,
+                name,
+                directory,
+                description,
+                example: example.map(redact),
+            },
+            ConfigError::Nested { path, error } => ConfigError::Nested {
+                path,
+                error: Box::new(error.redacted()),
+            },
+            error => error,
+        }
+    }
 }
 
 impl fmt::Display for ConfigError {
@@ -38,6 +252,24 @@ impl fmt::Display for ConfigError {
                 }
                 Ok(())
             }
+            ConfigError::MissingConditional {
+                key,
+                description,
+                example,
+                condition,
+            } => {
+                writeln!(
+                    f,
+                    "{}: Is missing from environment and is required because {}",
+                    key.magenta().bold(),
+                    condition
+                )?;
+                writeln!(f, "\tDescription: {}", description)?;
+                if let Some(ex) = example {
+                    writeln!(f, "\tExample: {}={}", key.magenta().bold(), ex.cyan())?;
+                }
+                Ok(())
+            }
             ConfigError::InvalidEnvironment {
                 key,
                 value,
@@ -56,11 +288,201 @@ impl fmt::Display for ConfigError {
                 }
                 Ok(())
             }
+            ConfigError::InvalidValue {
+                key,
+                value,
+                reason,
+                description,
+                example,
+            } => {
+                writeln!(
+                    f,
+                    "{}: Invalid value {}",
+                    key.magenta().bold(),
+                    format!("'{}'", value).red(),
+                )?;
+                writeln!(f, "\tReason: {}", reason)?;
+                writeln!(f, "\tDescription: {}", description)?;
+                if let Some(ex) = example {
+                    writeln!(f, "\tExample: {}={}", key.magenta().bold(), ex.cyan())?;
+                }
+                Ok(())
+            }
+            ConfigError::ValidationFailed {
+                key,
+                value,
+                reason,
+                description,
+            } => {
+                writeln!(
+                    f,
+                    "{}: Value {} is not allowed",
+                    key.magenta().bold(),
+                    format!("'{}'", value).red(),
+                )?;
+                writeln!(f, "\tReason: {}", reason)?;
+                writeln!(f, "\tDescription: {}", description)
+            }
+            ConfigError::Inconsistent { keys, reason } if keys.is_empty() => {
+                writeln!(f, "{}", reason)
+            }
+            ConfigError::Inconsistent { keys, reason } => {
+                writeln!(f, "{}: {}", keys.join(", ").magenta().bold(), reason)
+            }
+            ConfigError::ConflictingFields { set, group } => {
+                writeln!(
+                    f,
+                    "{}: Are set together, but only one of them can be set",
+                    set.join(", ").magenta().bold()
+                )?;
+                writeln!(f, "\tGroup: {}", group.join(", "))
+            }
+            ConfigError::MissingOneOf { group } => {
+                writeln!(
+                    f,
+                    "{}: None of these is set, but one of them is required",
+                    group.join(", ").magenta().bold()
+                )
+            }
+            ConfigError::FileError { path, message } => {
+                writeln!(
+                    f,
+                    "{}: Could not load configuration file",
+                    path.display().to_string().magenta().bold()
+                )?;
+                writeln!(f, "\tReason: {}", message)
+            }
+            ConfigError::InvalidFileValue {
+                path,
+                key,
+                value,
+                description,
+                example,
+            } => {
+                writeln!(
+                    f,
+                    "{}: Invalid value {} for key {}",
+                    path.display().to_string().magenta().bold(),
+                    format!("'{}'", value).red(),
+                    key.magenta().bold(),
+                )?;
+                writeln!(f, "\tDescription: {}", description)?;
+                if let Some(ex) = example {
+                    writeln!(f, "\tExample: {} = {}", key.magenta().bold(), ex.cyan())?;
+                }
+                writeln!(f, "\tOverride with: {}", key.magenta().bold())
+            }
+            ConfigError::UnreadableFile {
+                key,
+                path,
+                message,
+                description,
+            } => {
+                writeln!(
+                    f,
+                    "{}: Could not read file {}",
+                    key.magenta().bold(),
+                    path.display().to_string().red(),
+                )?;
+                writeln!(f, "\tReason: {}", message)?;
+                writeln!(f, "\tDescription: {}", description)
+            }
+            ConfigError::ConflictingAliases {
+                key,
+                set,
+                description,
+            } => {
+                writeln!(
+                    f,
+                    "{}: Is set under several names with different values: {}",
+                    key.magenta().bold(),
+                    set.join(", ").red()
+                )?;
+                writeln!(f, "\tSet only {}, the others are deprecated", key)?;
+                writeln!(f, "\tDescription: {}", description)
+            }
+            ConfigError::UnresolvedSecret {
+                key,
+                reference,
+                message,
+                description,
+            } => {
+                writeln!(
+                    f,
+                    "{}: Could not resolve secret reference {}",
+                    key.magenta().bold(),
+                    format!("'{}'", reference).red(),
+                )?;
+                writeln!(f, "\tReason: {}", message)?;
+                writeln!(f, "\tDescription: {}", description)
+            }
+            ConfigError::MissingCredential {
+                key,
+                name,
+                directory,
+                description,
+                example,
+            } => {
+                writeln!(
+                    f,
+                    "{}: Is missing from environment and credential {} was not found",
+                    key.magenta().bold(),
+                    name.magenta().bold(),
+                )?;
+                match directory {
+                    Some(dir) => writeln!(f, "\tSearched: {}", dir.join(name).display())?,
+                    None => writeln!(f, "\tReason: $CREDENTIALS_DIRECTORY is not set")?,
+                }
+                writeln!(f, "\tDescription: {}", description)?;
+                if let Some(ex) = example {
+                    writeln!(f, "\tExample: {}={}", key.magenta().bold(), ex.cyan())?;
+                }
+                Ok(())
+            }
+            ConfigError::UnknownFileKey { path, key } => {
+                writeln!(
+                    f,
+                    "{}: Unknown key {}",
+                    path.display().to_string().magenta().bold(),
+                    key.magenta().bold(),
+                )?;
+                writeln!(
+                    f,
+                    "\tThe key doesn't match any configuration field, check for typos"
+                )
+            }
+            ConfigError::GroupIndexGap {
+                prefix,
+                missing,
+                found,
+            } => {
+                writeln!(
+                    f,
+                    "{}: Group {} is set but group {} is missing",
+                    format!("{}<n>_", prefix).magenta().bold(),
+                    format!("{}{}_", prefix, found).red(),
+                    format!("{}{}_", prefix, missing).magenta().bold(),
+                )?;
+                writeln!(
+                    f,
+                    "\tGroups must be numbered from 0 without gaps, groups from {} on are ignored",
+                    missing
+                )
+            }
+            ConfigError::Warning { warning } => write!(f, "{}", warning),
+            ConfigError::Nested { path, error } => write!(f, "{} / {}", path.cyan(), error),
         }
     }
 }
 
-impl std::error::Error for ConfigError {}
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Nested { error, .. } => Some(&**error),
+            _ => None,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -98,6 +520,21 @@ mod tests {
         assert!(!output.contains("Example:"));
     }
 
+    #[test]
+    fn test_missing_conditional() {
+        colored::control::set_override(false);
+        let error = ConfigError::MissingConditional {
+            key: "SMTP_HOST".to_string(),
+            description: "SMTP server".to_string(),
+            example: None,
+            condition: "EMAIL_ENABLED is true".to_string(),
+        };
+
+        assert!(error.to_string().contains(
+            "SMTP_HOST: Is missing from environment and is required because EMAIL_ENABLED is true"
+        ));
+    }
+
     #[test]
     fn test_invalid_environment() {
         colored::control::set_override(false);
@@ -116,6 +553,98 @@ mod tests {
         assert!(output.contains("Example: PORT=8080"));
     }
 
+    #[test]
+    fn test_invalid_value_shows_reason() {
+        colored::control::set_override(false);
+
+        let error = ConfigError::InvalidValue {
+            key: "PORTS".to_string(),
+            value: "80,http".to_string(),
+            reason: "element at index 1 ('http'): invalid digit found in string".to_string(),
+            description: "Ports to listen on".to_string(),
+            example: Some("80,443".to_string()),
+        };
+
+        let output = error.to_string();
+        assert!(output.contains("PORTS: Invalid value '80,http'"));
+        assert!(output.contains("Reason: element at index 1 ('http')"));
+        assert!(output.contains("Example: PORTS=80,443"));
+    }
+
+    #[test]
+    fn test_validation_failed_display() {
+        colored::control::set_override(false);
+        let error = ConfigError::ValidationFailed {
+            key: "PORT".to_string(),
+            value: "0".to_string(),
+            reason: "must be in range 1..=65535".to_string(),
+            description: "Server port".to_string(),
+        };
+
+        let output = error.to_string();
+        assert!(output.contains("PORT: Value '0' is not allowed"));
+        assert!(output.contains("Reason: must be in range 1..=65535"));
+        assert!(output.contains("Description: Server port"));
+    }
+
+    #[test]
+    fn test_inconsistent_display() {
+        colored::control::set_override(false);
+        let error = ConfigError::Inconsistent {
+            keys: vec!["TLS_CERT".to_string(), "TLS_KEY".to_string()],
+            reason: "must be set together".to_string(),
+        };
+
+        assert!(
+            error
+                .to_string()
+                .contains("TLS_CERT, TLS_KEY: must be set together")
+        );
+    }
+
+    #[test]
+    fn test_field_group_display() {
+        colored::control::set_override(false);
+        let error = ConfigError::ConflictingFields {
+            set: vec!["DATABASE_URL".to_string(), "DB_HOST".to_string()],
+            group: vec![
+                "DATABASE_URL".to_string(),
+                "DB_HOST".to_string(),
+                "DB_USER".to_string(),
+            ],
+        };
+        let output = error.to_string();
+        assert!(output.contains("DATABASE_URL, DB_HOST: Are set together"));
+        assert!(output.contains("Group: DATABASE_URL, DB_HOST, DB_USER"));
+
+        let error = ConfigError::MissingOneOf {
+            group: vec!["API_TOKEN".to_string(), "API_TOKEN_FILE".to_string()],
+        };
+        assert!(
+            error
+                .to_string()
+                .contains("API_TOKEN, API_TOKEN_FILE: None of these is set")
+        );
+    }
+
+    #[test]
+    fn test_redacted() {
+        colored::control::set_override(false);
+        let error = ConfigError::InvalidValue {
+            key: "API_KEYS".to_string(),
+            value: "abc,,def".to_string(),
+            reason: "element at index 1 is empty".to_string(),
+            description: "API keys".to_string(),
+            example: Some("key1,key2".to_string()),
+        };
+
+        let output = error.redacted().to_string();
+        assert!(output.contains("API_KEYS: Invalid value '[REDACTED]'"));
+        assert!(output.contains("Reason: [REDACTED]"));
+        assert!(output.contains("Example: API_KEYS=[REDACTED]"));
+        assert!(!output.contains("abc"));
+    }
+
     #[test]
     fn test_clone() {
         let error1 = ConfigError::MissingEnvVar {
@@ -160,4 +689,158 @@ mod tests {
         assert!(output.contains("Must be valid format"));
         assert!(!output.contains("Example:"));
     }
+
+    #[test]
+    fn test_invalid_file_value() {
+        colored::control::set_override(false);
+
+        let error = ConfigError::InvalidFileValue {
+            path: PathBuf::from("config.toml"),
+            key: "port".to_string(),
+            value: "<array>".to_string(),
+            description: "Server port".to_string(),
+            example: Some("8080".to_string()),
+        };
+
+        let output = error.to_string();
+        assert!(output.contains("config.toml: Invalid value '<array>' for key port"));
+        assert!(output.contains("Description: Server port"));
+        assert!(output.contains("Example: port = 8080"));
+    }
+
+    #[test]
+    fn test_unknown_file_key() {
+        colored::control::set_override(false);
+
+        let error = ConfigError::UnknownFileKey {
+            path: PathBuf::from("config.toml"),
+            key: "prot".to_string(),
+        };
+
+        assert!(error.to_string().contains("config.toml: Unknown key prot"));
+    }
+
+    #[test]
+    fn test_missing_credential() {
+        colored::control::set_override(false);
+
+        let error = ConfigError::MissingCredential {
+            key: "DB_PASSWORD".to_string(),
+            name: "db_password".to_string(),
+            directory: Some(PathBuf::from("/run/credentials/app.service")),
+            description: "Database password".to_string(),
+            example: None,
+        };
+
+        let output = error.to_string();
+        assert!(output.contains(
+            "DB_PASSWORD: Is missing from environment and credential db_password was not found"
+        ));
+        assert!(output.contains("Searched: /run/credentials/app.service/db_password"));
+        assert!(output.contains("Description: Database password"));
+    }
+
+    #[test]
+    fn test_group_index_gap() {
+        colored::control::set_override(false);
+
+        let error = ConfigError::GroupIndexGap {
+            prefix: "WORKER_".to_string(),
+            missing: 1,
+            found: 2,
+        };
+
+        assert!(
+            error
+                .to_string()
+                .starts_with("WORKER_<n>_: Group WORKER_2_ is set but group WORKER_1_ is missing")
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        colored::control::set_override(false);
+
+        let error = ConfigError::Nested {
+            path: "db.url".to_string(),
+            error: Box::new(ConfigError::MissingEnvVar {
+                key: "DB_URL".to_string(),
+                description: "Database URL".to_string(),
+                example: None,
+            }),
+        };
+
+        let output = error.to_string();
+        assert!(output.starts_with("db.url / DB_URL: Is missing from environment"));
+        assert!(std::error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn test_unreadable_file() {
+        colored::control::set_override(false);
+
+        let error = ConfigError::UnreadableFile {
+            key: "DB_PASSWORD_FILE".to_string(),
+            path: PathBuf::from("/run/secrets/db_password"),
+            message: "No such file or directory".to_string(),
+            description: "Database password".to_string(),
+        };
+
+        let output = error.to_string();
+        assert!(output.contains("DB_PASSWORD_FILE: Could not read file /run/secrets/db_password"));
+        assert!(output.contains("Reason: No such file or directory"));
+        assert!(output.contains("Description: Database password"));
+    }
+
+    #[test]
+    fn test_unresolved_secret() {
+        colored::control::set_override(false);
+
+        let error = ConfigError::UnresolvedSecret {
+            key: "DB_PASSWORD".to_string(),
+            reference: "secretref://prod/db#password".to_string(),
+            message: "no such secret".to_string(),
+            description: "Database password".to_string(),
+        };
+
+        let output = error.to_string();
+        assert!(output.contains(
+            "DB_PASSWORD: Could not resolve secret reference 'secretref://prod/db#password'"
+        ));
+        assert!(output.contains("Reason: no such secret"));
+        assert!(output.contains("Description: Database password"));
+    }
+
+    #[test]
+    fn test_conflicting_aliases() {
+        colored::control::set_override(false);
+
+        let error = ConfigError::ConflictingAliases {
+            key: "DATABASE_URL".to_string(),
+            set: vec!["DATABASE_URL".to_string(), "DB_URL".to_string()],
+            description: "Database URL".to_string(),
+        };
+
+        let output = error.to_string();
+        assert!(output.contains(
+            "DATABASE_URL: Is set under several names with different values: DATABASE_URL, DB_URL"
+        ));
+        assert!(output.contains("Set only DATABASE_URL, the others are deprecated"));
+    }
+
+    #[test]
+    fn test_strict_warning() {
+        colored::control::set_override(false);
+
+        let error = ConfigError::Warning {
+            warning: ConfigWarning::DeprecatedAlias {
+                alias: "DB_URL".to_string(),
+                key: "DATABASE_URL".to_string(),
+            },
+        };
+        assert_eq!(
+            error.to_string(),
+            "DB_URL: Is deprecated, use DATABASE_URL instead\n"
+        );
+    }
 }
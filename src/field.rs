@@ -1,6 +1,9 @@
+use crate::secret::REDACTED;
+use std::fmt;
+
 /// Metadata for a configuration field (without the value)
-#[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub struct ConfigFieldMeta<T> {
     /// Environment variable key
     pub key: &'static str,
@@ -10,6 +13,9 @@ pub struct ConfigFieldMeta<T> {
     pub default: T,
     /// Whether this field is required (true) or optional with a default (false)
     pub required: bool,
+    /// Whether the value is secret, so `default` is redacted when printed or serialized
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub secret: bool,
 }
 
 impl<T> ConfigFieldMeta<T> {
@@ -19,6 +25,7 @@ impl<T> ConfigFieldMeta<T> {
             description,
             default: example,
             required: true,
+            secret: false,
         }
     }
 
@@ -28,10 +35,288 @@ impl<T> ConfigFieldMeta<T> {
             description,
             default,
             required: false,
+            secret: false,
+        }
+    }
+
+    /// Mark the value as secret
+    pub fn secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ConfigFieldMeta<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("ConfigFieldMeta");
+        debug
+            .field("key", &self.key)
+            .field("description", &self.description);
+        if self.secret {
+            debug.field("default", &format_args!("{}", REDACTED));
+        } else {
+            debug.field("default", &self.default);
+        }
+        debug
+            .field("required", &self.required)
+            .field("secret", &self.secret)
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ConfigFieldMeta<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("ConfigFieldMeta", 5)?;
+        state.serialize_field("key", self.key)?;
+        state.serialize_field("description", self.description)?;
+        if self.secret {
+            state.serialize_field("default", REDACTED)?;
+        } else {
+            state.serialize_field("default", &self.default)?;
+        }
+        state.serialize_field("required", &self.required)?;
+        state.serialize_field("secret", &self.secret)?;
+        state.end()
+    }
+}
+
+/// Extra options for loading a single field through `ConfigBuilder`
+///
+/// # Example
+/// ```rust
+/// use config_loadr::{ConfigBuilder, FieldOptions};
+///
+/// let mut builder = ConfigBuilder::new();
+/// let options = FieldOptions::new().name("port");
+/// let port = builder.or_default_with("PORT", "Server port", 8080u16, &options);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FieldOptions {
+    /// Struct field name, used as an alternative key by file layers
+    pub name: Option<&'static str>,
+    /// Read the value from the file named by `<KEY>_FILE` when `<KEY>` is not set
+    pub file_fallback: bool,
+    /// systemd credential name, read from `$CREDENTIALS_DIRECTORY` before the environment
+    pub credential: Option<&'static str>,
+    /// Constraints on the value, shown in generated docs
+    pub constraints: Vec<String>,
+    /// Report an optional field as missing when this condition holds
+    pub required_if: Option<Condition>,
+    /// Redact the value in errors, docs and provenance
+    pub secret: bool,
+    /// Deprecated keys still read when the key isn't set, in order
+    pub aliases: Vec<String>,
+    /// Element separator of a list field, joins arrays read from a file layer
+    pub separator: Option<&'static str>,
+}
+
+impl FieldOptions {
+    /// Create options with every setting at its default
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the struct field name
+    pub fn name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Read the value from the file named by `<KEY>_FILE` when `<KEY>` is not set
+    ///
+    /// This is the Docker/Kubernetes secrets convention, for example
+    /// `DATABASE_PASSWORD_FILE=/run/secrets/db_password`. The file content is trimmed.
+    pub fn file_fallback(mut self) -> Self {
+        self.file_fallback = true;
+        self
+    }
+
+    /// Read the value from the systemd credential `name` before the environment
+    ///
+    /// When the service runs with `LoadCredential=`, systemd sets
+    /// `$CREDENTIALS_DIRECTORY` and the value is read from
+    /// `$CREDENTIALS_DIRECTORY/<name>`. Without the directory, or when the
+    /// credential isn't in it, the environment variable is used instead.
+    pub fn credential(mut self, name: &'static str) -> Self {
+        self.credential = Some(name);
+        self
+    }
+
+    /// Redact the value in errors, docs and provenance
+    ///
+    /// Values, examples and defaults are replaced with `[REDACTED]`, and
+    /// parse errors don't include the parser's explanation since it may
+    /// quote the value.
+    pub fn secret(mut self) -> Self {
+        self.secret = true;
+        self
+    }
+
+    /// Require the field when `condition` holds
+    ///
+    /// The field still loads as an `Option`, a missing value is only reported
+    /// as [`ConfigError::MissingConditional`](crate::ConfigError::MissingConditional)
+    /// when the condition holds.
+    pub fn required_if(mut self, condition: Condition) -> Self {
+        self.required_if = Some(condition);
+        self
+    }
+
+    /// Require the field unless `condition` holds
+    pub fn required_unless(mut self, condition: Condition) -> Self {
+        self.required_if = Some(condition.negate());
+        self
+    }
+
+    /// Also read the value from a deprecated key, like `DB_URL` for `DATABASE_URL`
+    ///
+    /// Aliases are read after the key, in the order they were added. Reading
+    /// one collects a [`ConfigWarning::DeprecatedAlias`](crate::ConfigWarning::DeprecatedAlias)
+    /// naming the key to use instead, and setting names to different values
+    /// is an error. The warning is only returned by loaders that report
+    /// warnings, like `new_with_warnings()`, `new()` and `load()` drop it.
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Set the element separator of a list field
+    ///
+    /// Arrays in a file layer are joined with it, so the field parses them like
+    /// a value from the environment. Other fields reject arrays.
+    pub fn separator(mut self, separator: &'static str) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// Describe a constraint on the value for the generated docs, like `range 1..=65535`
+    ///
+    /// The constraint itself is checked with [`ConfigBuilder::check`](crate::ConfigBuilder::check).
+    pub fn constraint(mut self, constraint: impl Into<String>) -> Self {
+        self.constraints.push(constraint.into());
+        self
+    }
+}
+
+/// A condition on the value of another variable, for conditionally required fields
+///
+/// It's checked against the value the field with that key loaded, before
+/// parsing and including its default, or the raw sources if no earlier field
+/// reads the key.
+///
+/// # Example
+/// ```rust
+/// use config_loadr::{Condition, FieldOptions};
+///
+/// // SMTP_HOST is required when EMAIL_ENABLED=true
+/// let options = FieldOptions::new().required_if(Condition::truthy("EMAIL_ENABLED"));
+///
+/// // BUCKET is required when STORAGE=s3
+/// let options = FieldOptions::new().required_if(Condition::equals("STORAGE", "s3"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condition {
+    /// The variable the condition reads
+    pub key: String,
+    /// The value it must equal, or None for any truthy value
+    pub value: Option<String>,
+    /// Whether the condition is inverted
+    pub negated: bool,
+}
+
+impl Condition {
+    /// The variable is set to a truthy value: `true`, `1`, `yes` or `on`, in any case
+    pub fn truthy(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: None,
+            negated: false,
+        }
+    }
+
+    /// The variable is set to exactly `value`, ignoring surrounding whitespace
+    pub fn equals(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: Some(value.into()),
+            negated: false,
+        }
+    }
+
+    /// The inverse of this condition
+    pub fn negate(mut self) -> Self {
+        self.negated = !self.negated;
+        self
+    }
+
+    /// Whether the condition holds for the variable's raw value, None if unset
+    pub fn holds(&self, raw: Option<&str>) -> bool {
+        let matched = match (&self.value, raw.map(str::trim)) {
+            (_, None) => false,
+            (None, Some(raw)) => ["true", "1", "yes", "on"]
+                .iter()
+                .any(|truthy| raw.eq_ignore_ascii_case(truthy)),
+            (Some(value), Some(raw)) => raw == value,
+        };
+        matched != self.negated
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let not = if self.negated { "not " } else { "" };
+        match &self.value {
+            Some(value) => write!(f, "{} is {}'{}'", self.key, not, value),
+            None => write!(f, "{} is {}true", self.key, not),
         }
     }
 }
 
+/// How many fields of a group may be set, for [`ConfigBuilder::check_group`](crate::ConfigBuilder::check_group)
+///
+/// A field counts as set when its value came from anywhere but its default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupRule {
+    /// Exactly one field must be set, `#[config(exactly_one_of(...))]`
+    ExactlyOne,
+    /// At least one field must be set, `#[config(at_least_one_of(...))]`
+    AtLeastOne,
+    /// At most one field may be set, `#[config(conflicts(...))]`
+    AtMostOne,
+}
+
+impl GroupRule {
+    /// Describe the rule for a group of keys, for docs
+    pub fn describe(&self, keys: &[String]) -> String {
+        let keys = keys
+            .iter()
+            .map(|key| format!("`{}`", key))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self {
+            GroupRule::ExactlyOne => format!("Exactly one of {} must be set", keys),
+            GroupRule::AtLeastOne => format!("At least one of {} must be set", keys),
+            GroupRule::AtMostOne => format!("Only one of {} can be set", keys),
+        }
+    }
+}
+
+/// Prepend the prefix of a nested config to a metadata key
+///
+/// Metadata keys are `&'static str`, so prefixed keys are leaked. The generated
+/// `metadata()` builds its metadata once, so this happens once per nested field.
+#[doc(hidden)]
+pub fn prefixed_key(prefix: &str, key: &'static str) -> &'static str {
+    if prefix.is_empty() {
+        key
+    } else {
+        Box::leak(format!("{}{}", prefix, key).into_boxed_str())
+    }
+}
+
 // Re-export as ConfigField for backwards compatibility with macro internals
 // This will be used only for metadata, not values
 pub type ConfigField<T> = ConfigFieldMeta<T>;
@@ -40,6 +325,35 @@ pub type ConfigField<T> = ConfigFieldMeta<T>;
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_secret_meta_is_redacted() {
+        let meta = ConfigFieldMeta::required("API_KEY", "API key", "hunter2").secret();
+        let debug = format!("{:?}", meta);
+        assert!(debug.contains("default: [REDACTED]"));
+        assert!(!debug.contains("hunter2"));
+
+        let meta = ConfigFieldMeta::optional("PORT", "Port", 8080);
+        assert!(format!("{:?}", meta).contains("default: 8080"));
+    }
+
+    #[test]
+    fn test_condition_holds() {
+        let enabled = Condition::truthy("EMAIL_ENABLED");
+        assert!(enabled.holds(Some("true")));
+        assert!(enabled.holds(Some(" YES ")));
+        assert!(!enabled.holds(Some("false")));
+        assert!(!enabled.holds(None));
+        assert!(enabled.clone().negate().holds(None));
+
+        let s3 = Condition::equals("STORAGE", "s3");
+        assert!(s3.holds(Some("s3")));
+        assert!(!s3.holds(Some("S3")));
+        assert!(s3.clone().negate().holds(Some("local")));
+
+        assert_eq!(enabled.to_string(), "EMAIL_ENABLED is true");
+        assert_eq!(s3.negate().to_string(), "STORAGE is not 's3'");
+    }
+
     #[test]
     fn test_required_field_meta_creation() {
         let field = ConfigFieldMeta::required("PORT", "Server port", 8080);
@@ -0,0 +1,280 @@
+use crate::error::ConfigError;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// A configuration file layered underneath the primary source
+///
+/// Values in the file are looked up by field name first, then by environment
+/// variable key. The primary source (usually the environment) always wins, so
+/// environment variables can override anything in the file.
+///
+/// Errors reading or parsing the file are not returned here, they are reported
+/// together with all other configuration errors when the config is loaded.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "toml")] {
+/// use config_loadr::{ConfigBuilder, FieldOptions, FileLayer};
+///
+/// let file = FileLayer::toml_str("config.toml", "port = 3000");
+/// let mut builder = ConfigBuilder::new().with_file(file);
+/// let options = FieldOptions::new().name("port");
+/// let port = builder.or_default_with("APP_PORT", "Server port", 8080u16, &options);
+///
+/// assert_eq!(port, Some(3000));
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileLayer {
+    path: PathBuf,
+    values: BTreeMap<String, FileValue>,
+    error: Option<ConfigError>,
+    strict: bool,
+}
+
+/// A value read from a configuration file
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FileValue {
+    /// A scalar value converted to its string form
+    Scalar(String),
+    /// An array of scalars, joined with the separator of the list field reading it
+    #[cfg_attr(not(feature = "toml"), allow(dead_code))]
+    List(Vec<String>),
+    /// A value that can't be used for a single field, holds the type name
+    ///
+    /// Only TOML files have values like tables and arrays.
+    #[cfg_attr(not(feature = "toml"), allow(dead_code))]
+    Unsupported(&'static str),
+}
+
+impl FileLayer {
+    /// Create a file layer from already flattened key/value pairs
+    ///
+    /// `path` is only used in error messages.
+    pub fn from_pairs<I, K, V>(path: impl AsRef<Path>, pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let values = pairs
+            .into_iter()
+            .map(|(k, v)| (k.into(), FileValue::Scalar(v.into())))
+            .collect();
+        Self::with_values(path, values)
+    }
+
+    /// Read a TOML file
+    ///
+    /// Tables are flattened into dotted keys, so `[db] url = "..."` is found
+    /// under `db.url`.
+    #[cfg(feature = "toml")]
+    pub fn toml(path: impl AsRef<Path>) -> Self {
+        match std::fs::read_to_string(path.as_ref()) {
+            Ok(content) => Self::toml_str(path, &content),
+            Err(e) => Self::failed(path, e.to_string()),
+        }
+    }
+
+    /// Parse TOML content, `path` is only used in error messages
+    #[cfg(feature = "toml")]
+    pub fn toml_str(path: impl AsRef<Path>, content: &str) -> Self {
+        match content.parse::<toml::Table>() {
+            Ok(table) => {
+                let mut values = BTreeMap::new();
+                flatten_toml("", &table, &mut values);
+                Self::with_values(path, values)
+            }
+            Err(e) => Self::failed(path, e.message().to_string()),
+        }
+    }
+
+    /// Report keys in the file that don't match any field as errors
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Path of the file this layer was read from
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether unknown keys are reported as errors
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    fn with_values(path: impl AsRef<Path>, values: BTreeMap<String, FileValue>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            values,
+            error: None,
+            strict: false,
+        }
+    }
+
+    #[cfg(feature = "toml")]
+    fn failed(path: impl AsRef<Path>, message: String) -> Self {
+        let path = path.as_ref().to_path_buf();
+        Self {
+            error: Some(ConfigError::FileError {
+                path: path.clone(),
+                message,
+            }),
+            path,
+            values: BTreeMap::new(),
+            strict: false,
+        }
+    }
+
+    /// Find a value by field name, then by environment key
+    pub(crate) fn lookup(&self, name: Option<&str>, key: &str) -> Option<(&str, &FileValue)> {
+        name.into_iter()
+            .chain([key])
+            .find_map(|k| self.values.get_key_value(k))
+            .map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Error from reading or parsing the file, if any
+    pub(crate) fn error(&self) -> Option<&ConfigError> {
+        self.error.as_ref()
+    }
+
+    /// Keys in the file for which `is_known` returns false
+    pub(crate) fn unknown_keys(&self, is_known: impl Fn(&str) -> bool) -> Vec<ConfigError> {
+        self.values
+            .keys()
+            .filter(|key| !is_known(key))
+            .map(|key| ConfigError::UnknownFileKey {
+                path: self.path.clone(),
+                key: key.clone(),
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "toml")]
+fn flatten_toml(prefix: &str, table: &toml::Table, out: &mut BTreeMap<String, FileValue>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        let value = match value {
+            toml::Value::Table(table) => {
+                flatten_toml(&key, table, out);
+                continue;
+            }
+            toml::Value::Array(array) => match array.iter().map(toml_scalar).collect() {
+                Some(items) => FileValue::List(items),
+                None => FileValue::Unsupported("array"),
+            },
+            scalar => FileValue::Scalar(toml_scalar(scalar).unwrap_or_default()),
+        };
+        out.insert(key, value);
+    }
+}
+
+/// The string form of a scalar TOML value, None for tables and arrays
+#[cfg(feature = "toml")]
+fn toml_scalar(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(i) => Some(i.to_string()),
+        toml::Value::Float(f) => Some(f.to_string()),
+        toml::Value::Boolean(b) => Some(b.to_string()),
+        toml::Value::Datetime(d) => Some(d.to_string()),
+        toml::Value::Array(_) | toml::Value::Table(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_pairs_lookup_by_name_then_key() {
+        let file = FileLayer::from_pairs("app.conf", [("port", "1"), ("PORT", "2")]);
+
+        assert_eq!(
+            file.lookup(Some("port"), "PORT"),
+            Some(("port", &FileValue::Scalar("1".to_string())))
+        );
+        assert_eq!(
+            file.lookup(None, "PORT"),
+            Some(("PORT", &FileValue::Scalar("2".to_string())))
+        );
+        assert_eq!(file.lookup(Some("host"), "HOST"), None);
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        let file = FileLayer::from_pairs("app.conf", [("port", "1"), ("prot", "2")]).strict();
+
+        let errors = file.unknown_keys(|key| key == "port");
+        assert!(file.is_strict());
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            ConfigError::UnknownFileKey { key, .. } if key == "prot"
+        ));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_values_are_flattened() {
+        let file = FileLayer::toml_str(
+            "config.toml",
+            "port = 8080\ndebug = true\nratio = 0.5\nhosts = [\"a\", \"b\"]\nmatrix = [[1]]\n\n[db]\nurl = \"postgres://\"\n",
+        );
+
+        assert!(file.error().is_none());
+        assert_eq!(
+            file.lookup(Some("port"), "PORT").map(|(_, v)| v),
+            Some(&FileValue::Scalar("8080".to_string()))
+        );
+        assert_eq!(
+            file.lookup(Some("debug"), "DEBUG").map(|(_, v)| v),
+            Some(&FileValue::Scalar("true".to_string()))
+        );
+        assert_eq!(
+            file.lookup(Some("ratio"), "RATIO").map(|(_, v)| v),
+            Some(&FileValue::Scalar("0.5".to_string()))
+        );
+        assert_eq!(
+            file.lookup(Some("db.url"), "DB_URL").map(|(_, v)| v),
+            Some(&FileValue::Scalar("postgres://".to_string()))
+        );
+        assert_eq!(
+            file.lookup(Some("hosts"), "HOSTS").map(|(_, v)| v),
+            Some(&FileValue::List(vec!["a".to_string(), "b".to_string()]))
+        );
+        assert_eq!(
+            file.lookup(Some("matrix"), "MATRIX").map(|(_, v)| v),
+            Some(&FileValue::Unsupported("array"))
+        );
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_parse_error() {
+        let file = FileLayer::toml_str("broken.toml", "port = ");
+
+        assert!(matches!(
+            file.error(),
+            Some(ConfigError::FileError { path, .. }) if path == Path::new("broken.toml")
+        ));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_toml_missing_file() {
+        let file = FileLayer::toml("/nonexistent/config-loadr/config.toml");
+        assert!(matches!(file.error(), Some(ConfigError::FileError { .. })));
+    }
+}
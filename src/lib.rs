@@ -1,26 +1,133 @@
 pub mod builder;
+pub mod directory;
+pub mod dotenv;
 pub mod environment;
 pub mod error;
 pub mod field;
+pub mod file;
 pub mod macros;
+pub mod parse;
+pub mod provenance;
+pub mod resolve;
+pub mod secret;
+pub mod source;
+#[cfg(test)]
+mod test_util;
+pub mod validate;
+pub mod warning;
 
 // Re-export main types
-pub use builder::{ConfigBuilder, env_or_default, env_or_option, env_parse, env_required};
+pub use builder::{
+    ConfigBuilder, env_or_default, env_or_option, env_parse, env_parse_secret, env_required,
+    source_parse, source_parse_secret,
+};
+pub use directory::DirectorySource;
 pub use environment::Environment;
 pub use error::ConfigError;
-pub use field::{ConfigField, ConfigFieldMeta};
+pub use field::{Condition, ConfigField, ConfigFieldMeta, FieldOptions, GroupRule};
+pub use file::FileLayer;
+pub use parse::ParseError;
+pub use provenance::{FieldProvenance, Provenance, ProvenanceReport};
+pub use resolve::{FileResolver, SecretResolver};
+pub use secret::Secret;
+#[cfg(feature = "zeroize")]
+pub use secret::ZeroizingSecret;
+pub use source::{EnvSource, Precedence, Source, SourceError};
+pub use warning::ConfigWarning;
 
 // Re-export macro
 pub use config_loadr_macros::define_config;
 
+use std::collections::HashMap;
+
 /// Trait for loading configuration from environment variables
 pub trait Load: Sized {
+    /// Metadata for every field, generated as `<Struct>Meta`
+    type Meta: 'static;
+
     /// Load configuration from environment, panicking on validation errors
     fn load() -> Self;
 
     /// Load configuration from environment, returning errors instead of panicking
+    ///
+    /// Warnings, like a value read from a deprecated alias, are dropped. See
+    /// [`Load::new_with_warnings`].
     fn new() -> Result<Self, Vec<ConfigError>>;
 
+    /// Load configuration from environment, returning warnings with the config
+    ///
+    /// `new()` and `load()` don't report warnings.
+    fn new_with_warnings() -> Result<(Self, Vec<ConfigWarning>), Vec<ConfigError>>;
+
+    /// Load configuration from environment, failing on warnings as well as errors
+    ///
+    /// Dotenv files are loaded like `new()` does. See [`ConfigBuilder::strict`].
+    fn new_strict() -> Result<Self, Vec<ConfigError>>;
+
+    /// Load configuration from environment, returning where each field's value came from
+    ///
+    /// For other sources, load through a builder with [`Load::load_into`] and
+    /// read [`ConfigBuilder::provenance`].
+    fn new_with_provenance() -> Result<(Self, ProvenanceReport), Vec<ConfigError>>;
+
+    /// Load configuration from a custom source, panicking on validation errors
+    fn load_from<S: Source + ?Sized>(source: &S) -> Self;
+
+    /// Load configuration from a custom source, returning errors instead of panicking
+    fn new_from<S: Source + ?Sized>(source: &S) -> Result<Self, Vec<ConfigError>>;
+
+    /// Load configuration from environment with a configuration file underneath,
+    /// panicking on validation errors
+    fn load_with_file(file: FileLayer) -> Self;
+
+    /// Load configuration from environment with a configuration file underneath,
+    /// returning errors instead of panicking
+    ///
+    /// Environment variables take precedence over values in the file.
+    fn new_with_file(file: FileLayer) -> Result<Self, Vec<ConfigError>>;
+
+    /// Load configuration through a prepared builder, panicking on validation errors
+    fn load_with_builder(builder: ConfigBuilder<'_>) -> Self;
+
+    /// Load configuration through a prepared builder, returning errors instead of panicking
+    ///
+    /// Use this to combine sources, for example a [`DirectorySource`] layered
+    /// with [`ConfigBuilder::with_layer`]. Does not load `.env` files.
+    fn new_with_builder(builder: ConfigBuilder<'_>) -> Result<Self, Vec<ConfigError>>;
+
+    /// Load configuration from an in-memory map instead of the process environment
+    ///
+    /// Runs the same parsing, defaulting and error collection as `new()`, but
+    /// never reads or modifies the process environment and skips `.env` loading.
+    fn new_from_map(map: &HashMap<String, String>) -> Result<Self, Vec<ConfigError>> {
+        Self::new_from(map)
+    }
+
+    /// Load configuration from key/value pairs instead of the process environment
+    ///
+    /// See [`Load::new_from_map`].
+    fn from_iter<I, K, V>(iter: I) -> Result<Self, Vec<ConfigError>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let map: HashMap<String, String> = iter
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        Self::new_from_map(&map)
+    }
+
+    /// Load every field through an existing builder
+    ///
+    /// Returns None if any field failed, the errors are collected in the builder.
+    fn load_into(builder: &mut ConfigBuilder<'_>) -> Option<Self>;
+
     /// Create a builder for documentation generation (without loading values)
-    fn builder_for_docs() -> ConfigBuilder;
+    fn builder_for_docs() -> ConfigBuilder<'static>;
+
+    /// Build the metadata with every key prefixed, used when nesting this config
+    #[doc(hidden)]
+    fn metadata_with_prefix(prefix: &str) -> Self::Meta;
 }
@@ -5,3 +5,63 @@
 /// This is used by the generated code to check defaults
 #[doc(hidden)]
 pub const fn validate_const<T>(_value: &T) {}
+
+/// The `matches` validator, a compile error naming the feature when `regex` is off
+#[cfg(feature = "regex")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __validate_matches {
+    ($value:expr, $pattern:expr) => {
+        $crate::validate::matches($value, $pattern)
+    };
+}
+
+/// The `matches` validator, a compile error naming the feature when `regex` is off
+#[cfg(not(feature = "regex"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __validate_matches {
+    ($value:expr, $pattern:expr) => {
+        compile_error!("`matches` needs the `regex` feature of config-loadr")
+    };
+}
+
+/// The parser of `json` fields, a compile error naming the feature when `serde` is off
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __parse_json {
+    ($ty:ty) => {
+        $crate::parse::json::<$ty>
+    };
+}
+
+/// The parser of `json` fields, a compile error naming the feature when `serde` is off
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __parse_json {
+    ($ty:ty) => {
+        compile_error!("`#[field(json)]` needs the `serde` feature of config-loadr")
+    };
+}
+
+/// The docs renderer of `json` fields, `__parse_json!` reports a missing `serde` feature
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __display_json {
+    ($ty:ty) => {
+        $crate::parse::display_json::<$ty>
+    };
+}
+
+/// The docs renderer of `json` fields, `__parse_json!` reports a missing `serde` feature
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __display_json {
+    ($ty:ty) => {
+        |_: &$ty| ::std::string::String::new()
+    };
+}
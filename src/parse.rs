@@ -0,0 +1,339 @@
+//! Parsers for field values that aren't a single `FromStr` value
+//!
+//! `ConfigBuilder`'s `*_parsed` methods take any `Fn(&str) -> Result<T, ParseError>`.
+//! The functions here are the ones `define_config!` generates for list, map and JSON fields,
+//! along with the matching functions that render a default for the docs.
+
+use std::{fmt, str::FromStr};
+
+/// Why a raw configuration value could not be parsed
+///
+/// An error without a reason is reported like any invalid value, one with a
+/// reason is reported as [`ConfigError::InvalidValue`](crate::ConfigError::InvalidValue)
+/// so the reason is shown alongside it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    reason: Option<String>,
+}
+
+impl ParseError {
+    /// The value is invalid, with no further explanation
+    pub const fn invalid() -> Self {
+        Self { reason: None }
+    }
+
+    /// The value is invalid for the given reason
+    pub fn new(reason: impl fmt::Display) -> Self {
+        Self {
+            reason: Some(reason.to_string()),
+        }
+    }
+
+    /// Why the value is invalid, if known
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+}
+
+/// Parse a single value with `FromStr`, like plain fields
+pub fn from_str<T: FromStr>(value: &str) -> Result<T, ParseError> {
+    value.parse().map_err(|_| ParseError::invalid())
+}
+
+/// Parse a value with a custom function, like `#[field(parse_with = ...)]`
+///
+/// The function's error becomes the reason the value is invalid.
+pub fn with<T, E: fmt::Display>(
+    value: &str,
+    parse: impl FnOnce(&str) -> Result<T, E>,
+) -> Result<T, ParseError> {
+    parse(value).map_err(ParseError::new)
+}
+
+/// Parse a list of values separated by `separator`, like `a, b, c`
+///
+/// Every element is trimmed and parsed with `FromStr`. An empty value is an
+/// empty list, an empty element is an error. Errors name the index of the
+/// element that failed.
+pub fn list<C, T>(value: &str, separator: &str) -> Result<C, ParseError>
+where
+    C: FromIterator<T>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    if value.trim().is_empty() {
+        return Ok(std::iter::empty().collect());
+    }
+
+    value
+        .split(separator)
+        .map(str::trim)
+        .enumerate()
+        .map(|(index, element)| {
+            if element.is_empty() {
+                return Err(ParseError::new(format!(
+                    "element at index {} is empty",
+                    index
+                )));
+            }
+            element.parse().map_err(|e| {
+                ParseError::new(format!("element at index {} ('{}'): {}", index, element, e))
+            })
+        })
+        .collect()
+}
+
+/// Render a list the way [`list`] parses it, for docs
+pub fn display_list<'a, T: fmt::Display + 'a>(
+    items: impl IntoIterator<Item = &'a T>,
+    separator: &str,
+) -> String {
+    items
+        .into_iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Render an unordered collection like [`display_list`], with sorted elements
+///
+/// A `HashSet` iterates in a different order on every run, sorting keeps
+/// generated docs stable.
+pub fn display_set<'a, T: fmt::Display + 'a>(
+    items: impl IntoIterator<Item = &'a T>,
+    separator: &str,
+) -> String {
+    let mut items: Vec<String> = items.into_iter().map(ToString::to_string).collect();
+    items.sort();
+    items.join(separator)
+}
+
+/// Parse `key=value` pairs separated by `pair_separator`, like `a=1, b=2`
+///
+/// Keys and values are trimmed and parsed with `FromStr`, a value is split at
+/// the first `kv_separator` so it may contain the separator itself. An empty
+/// value is an empty map. Errors name the pair that failed, including a key
+/// that was already set.
+pub fn map<C, K, V>(value: &str, pair_separator: &str, kv_separator: &str) -> Result<C, ParseError>
+where
+    C: FromIterator<(K, V)>,
+    K: FromStr + PartialEq,
+    K::Err: fmt::Display,
+    V: FromStr,
+    V::Err: fmt::Display,
+{
+    if value.trim().is_empty() {
+        return Ok(std::iter::empty().collect());
+    }
+
+    let mut pairs: Vec<(K, V)> = Vec::new();
+    for (index, pair) in value.split(pair_separator).map(str::trim).enumerate() {
+        let Some((key, val)) = pair.split_once(kv_separator) else {
+            return Err(ParseError::new(format!(
+                "pair at index {} ('{}'): expected key{}value",
+                index, pair, kv_separator
+            )));
+        };
+        let (key, val) = (key.trim(), val.trim());
+        if key.is_empty() {
+            return Err(ParseError::new(format!(
+                "pair at index {} ('{}'): key is empty",
+                index, pair
+            )));
+        }
+
+        let parsed_key: K = key.parse().map_err(|e| {
+            ParseError::new(format!("pair at index {} ('{}'): key: {}", index, pair, e))
+        })?;
+        if let Some(first) = pairs.iter().position(|(k, _)| *k == parsed_key) {
+            return Err(ParseError::new(format!(
+                "pair at index {} ('{}'): duplicate key '{}', already set at index {}",
+                index, pair, key, first
+            )));
+        }
+        let parsed_value: V = val.parse().map_err(|e| {
+            ParseError::new(format!(
+                "pair at index {} ('{}'): value: {}",
+                index, pair, e
+            ))
+        })?;
+        pairs.push((parsed_key, parsed_value));
+    }
+
+    Ok(pairs.into_iter().collect())
+}
+
+/// Render a map the way [`map`] parses it, for docs
+///
+/// Pairs are sorted so the output doesn't depend on `HashMap` order.
+pub fn display_map<'a, K, V>(
+    items: impl IntoIterator<Item = (&'a K, &'a V)>,
+    pair_separator: &str,
+    kv_separator: &str,
+) -> String
+where
+    K: fmt::Display + 'a,
+    V: fmt::Display + 'a,
+{
+    let mut pairs: Vec<String> = items
+        .into_iter()
+        .map(|(key, value)| format!("{}{}{}", key, kv_separator, value))
+        .collect();
+    pairs.sort();
+    pairs.join(pair_separator)
+}
+
+/// Parse a JSON document with serde, for structured values in a single variable
+///
+/// The reason includes serde's message and the line and column it failed at.
+#[cfg(feature = "serde")]
+pub fn json<T: serde::de::DeserializeOwned>(value: &str) -> Result<T, ParseError> {
+    serde_json::from_str(value).map_err(|e| {
+        ParseError::new(format!(
+            "invalid JSON at line {}, column {}: {}",
+            e.line(),
+            e.column(),
+            strip_position(&e)
+        ))
+    })
+}
+
+/// serde_json's message without the `at line X column Y` suffix
+#[cfg(feature = "serde")]
+fn strip_position(error: &serde_json::Error) -> String {
+    let message = error.to_string();
+    let suffix = format!(" at line {} column {}", error.line(), error.column());
+    message
+        .strip_suffix(&suffix)
+        .map(str::to_string)
+        .unwrap_or(message)
+}
+
+/// Render a value as compact JSON, for docs
+#[cfg(feature = "serde")]
+pub fn display_json<T: serde::Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, HashMap, HashSet};
+
+    #[test]
+    fn test_list_trims_elements() {
+        let parsed: Vec<u16> = list(" 80, 443 ,8080", ",").unwrap();
+        assert_eq!(parsed, vec![80, 443, 8080]);
+
+        let parsed: HashSet<String> = list("a;b;a", ";").unwrap();
+        assert_eq!(parsed.len(), 2);
+
+        let parsed: Vec<u16> = list("  ", ",").unwrap();
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn test_list_names_failing_element() {
+        let error = list::<Vec<u16>, u16>("80,http,443", ",").unwrap_err();
+        assert_eq!(
+            error.reason(),
+            Some("element at index 1 ('http'): invalid digit found in string")
+        );
+
+        let error = list::<Vec<u16>, u16>("80,,443", ",").unwrap_err();
+        assert_eq!(error.reason(), Some("element at index 1 is empty"));
+    }
+
+    #[test]
+    fn test_display_list() {
+        assert_eq!(display_list(&vec![80, 443], ","), "80,443");
+        assert_eq!(display_list(&Vec::<u16>::new(), ","), "");
+    }
+
+    #[test]
+    fn test_display_set_is_sorted() {
+        let items: HashSet<&str> = ["d", "a", "c", "b"].into_iter().collect();
+        assert_eq!(display_set(&items, ","), "a,b,c,d");
+    }
+
+    #[test]
+    fn test_map_splits_at_first_separator() {
+        let parsed: BTreeMap<String, u32> = map(" a = 1, b=2 ", ",", "=").unwrap();
+        assert_eq!(parsed, BTreeMap::from([("a".into(), 1), ("b".into(), 2)]));
+
+        let parsed: HashMap<String, String> = map("x-token:a=b;x-id:1", ";", ":").unwrap();
+        assert_eq!(parsed["x-token"], "a=b");
+
+        let parsed: HashMap<String, String> = map("", ",", "=").unwrap();
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn test_map_names_failing_pair() {
+        let error = map::<HashMap<String, u32>, String, u32>("a=1,b", ",", "=").unwrap_err();
+        assert_eq!(
+            error.reason(),
+            Some("pair at index 1 ('b'): expected key=value")
+        );
+
+        let error = map::<HashMap<String, u32>, String, u32>("a=1,=2", ",", "=").unwrap_err();
+        assert_eq!(error.reason(), Some("pair at index 1 ('=2'): key is empty"));
+
+        let error = map::<HashMap<u16, u32>, u16, u32>("1=1,x=2", ",", "=").unwrap_err();
+        assert_eq!(
+            error.reason(),
+            Some("pair at index 1 ('x=2'): key: invalid digit found in string")
+        );
+
+        let error = map::<HashMap<String, u32>, String, u32>("a=1,b=x", ",", "=").unwrap_err();
+        assert_eq!(
+            error.reason(),
+            Some("pair at index 1 ('b=x'): value: invalid digit found in string")
+        );
+    }
+
+    #[test]
+    fn test_map_rejects_duplicate_keys() {
+        let error = map::<HashMap<u16, u32>, u16, u32>("1=1, 2=2, 01=3", ",", "=").unwrap_err();
+        assert_eq!(
+            error.reason(),
+            Some("pair at index 2 ('01=3'): duplicate key '01', already set at index 0")
+        );
+    }
+
+    #[test]
+    fn test_display_map_is_sorted() {
+        let items = HashMap::from([("b", 2), ("a", 1)]);
+        assert_eq!(display_map(&items, ",", "="), "a=1,b=2");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_reports_position() {
+        let parsed: BTreeMap<String, u32> = json(r#"{"a": 1}"#).unwrap();
+        assert_eq!(parsed["a"], 1);
+        assert_eq!(display_json(&parsed), r#"{"a":1}"#);
+
+        let error = json::<BTreeMap<String, u32>>("{\n  \"a\": \"x\"\n}").unwrap_err();
+        assert_eq!(
+            error.reason(),
+            Some("invalid JSON at line 2, column 10: invalid type: string \"x\", expected u32")
+        );
+    }
+
+    #[test]
+    fn test_with_keeps_error_message() {
+        let parse = |value: &str| u32::from_str_radix(value.trim_start_matches("0x"), 16);
+        assert_eq!(with("0x1f", parse), Ok(31));
+        assert_eq!(
+            with("0xzz", parse).unwrap_err().reason(),
+            Some("invalid digit found in string")
+        );
+    }
+
+    #[test]
+    fn test_from_str_has_no_reason() {
+        assert_eq!(from_str::<u16>("80"), Ok(80));
+        assert_eq!(from_str::<u16>("http"), Err(ParseError::invalid()));
+    }
+}
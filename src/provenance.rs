@@ -0,0 +1,210 @@
+use std::{fmt, path::PathBuf};
+
+/// Where a loaded configuration value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Provenance {
+    /// The field was not set anywhere and has no default
+    Missing,
+    /// The field's default value was used
+    Default,
+    /// Read from the process environment
+    Env,
+    /// Read from the process environment, loaded there from a dotenv file
+    Dotenv { path: PathBuf, line: usize },
+    /// Read from the file named by a `<KEY>_FILE` variable
+    EnvFile { key: String, path: PathBuf },
+    /// Read from a configuration file layer
+    File { path: PathBuf, key: String },
+    /// Read from a file in a directory source, one file per key
+    Directory { path: PathBuf },
+    /// Read from a systemd credential in `$CREDENTIALS_DIRECTORY`
+    Credential { path: PathBuf },
+    /// Read from a custom `Source`
+    Source,
+    /// Read from a deprecated alias of the field's key
+    Alias {
+        /// The deprecated key that was set
+        alias: String,
+        /// Where the alias was read from
+        provenance: Box<Provenance>,
+    },
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Provenance::Missing => write!(f, "missing"),
+            Provenance::Default => write!(f, "default"),
+            Provenance::Env => write!(f, "environment"),
+            Provenance::Dotenv { path, line } => write!(f, "{}:{}", path.display(), line),
+            Provenance::EnvFile { key, path } => write!(f, "{} (via {})", path.display(), key),
+            Provenance::File { path, key } => write!(f, "{} ({})", path.display(), key),
+            Provenance::Directory { path } => write!(f, "{}", path.display()),
+            Provenance::Credential { path } => write!(f, "{} (credential)", path.display()),
+            Provenance::Source => write!(f, "custom source"),
+            Provenance::Alias { alias, provenance } => {
+                write!(f, "{} (deprecated {})", provenance, alias)
+            }
+        }
+    }
+}
+
+/// Where a single field's value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldProvenance {
+    /// Environment variable key
+    pub key: String,
+    /// Struct field name, if the field was registered by a config struct
+    pub name: Option<String>,
+    /// The value as it was read, or the default value's string form
+    pub value: Option<String>,
+    /// Where the value came from
+    pub provenance: Provenance,
+}
+
+/// Where every field of a loaded configuration came from
+///
+/// The `Display` implementation renders an aligned table, suitable for startup logs:
+///
+/// ```text
+/// KEY           SOURCE   VALUE
+/// PORT          default  8080
+/// DATABASE_URL  .env:3   postgresql://localhost/db
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProvenanceReport {
+    fields: Vec<FieldProvenance>,
+    dotenv_files: Vec<PathBuf>,
+}
+
+impl ProvenanceReport {
+    /// Create a report from the provenance of each field
+    pub const fn new(fields: Vec<FieldProvenance>) -> Self {
+        Self {
+            fields,
+            dotenv_files: Vec::new(),
+        }
+    }
+
+    /// Record the dotenv files the load read
+    pub fn with_dotenv_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.dotenv_files = files;
+        self
+    }
+
+    /// The dotenv files the load read, in load order
+    pub fn dotenv_files(&self) -> &[PathBuf] {
+        &self.dotenv_files
+    }
+
+    /// Find a field by environment variable key or field name
+    pub fn get(&self, key: &str) -> Option<&FieldProvenance> {
+        self.fields
+            .iter()
+            .find(|field| field.key == key || field.name.as_deref() == Some(key))
+    }
+
+    /// All fields in the order they were loaded
+    pub fn fields(&self) -> &[FieldProvenance] {
+        &self.fields
+    }
+
+    /// Whether the report has no fields, for example because nothing was loaded yet
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+impl fmt::Display for ProvenanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows: Vec<[String; 3]> = self
+            .fields
+            .iter()
+            .map(|field| {
+                [
+                    field.key.clone(),
+                    field.provenance.to_string(),
+                    field.value.clone().unwrap_or_else(|| "-".to_string()),
+                ]
+            })
+            .collect();
+
+        let header = ["KEY", "SOURCE", "VALUE"];
+        let key_width = rows
+            .iter()
+            .map(|row| row[0].len())
+            .chain([header[0].len()])
+            .max()
+            .unwrap_or(0);
+        let source_width = rows
+            .iter()
+            .map(|row| row[1].len())
+            .chain([header[1].len()])
+            .max()
+            .unwrap_or(0);
+
+        for [key, source, value] in [header.map(str::to_string)].into_iter().chain(rows) {
+            writeln!(f, "{:key_width$}  {:source_width$}  {}", key, source, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> ProvenanceReport {
+        ProvenanceReport::new(vec![
+            FieldProvenance {
+                key: "PORT".to_string(),
+                name: Some("port".to_string()),
+                value: Some("8080".to_string()),
+                provenance: Provenance::Default,
+            },
+            FieldProvenance {
+                key: "DATABASE_URL".to_string(),
+                name: Some("database_url".to_string()),
+                value: Some("postgresql://localhost/db".to_string()),
+                provenance: Provenance::Dotenv {
+                    path: PathBuf::from(".env"),
+                    line: 3,
+                },
+            },
+        ])
+    }
+
+    #[test]
+    fn test_get_by_key_or_name() {
+        let report = report();
+
+        assert_eq!(report.get("PORT").unwrap().provenance, Provenance::Default);
+        assert_eq!(report.get("port").unwrap().key, "PORT");
+        assert!(report.get("HOST").is_none());
+        assert!(!report.is_empty());
+    }
+
+    #[test]
+    fn test_provenance_display() {
+        assert_eq!(Provenance::Env.to_string(), "environment");
+        assert_eq!(
+            Provenance::File {
+                path: PathBuf::from("config.toml"),
+                key: "db.url".to_string()
+            }
+            .to_string(),
+            "config.toml (db.url)"
+        );
+    }
+
+    #[test]
+    fn test_report_table() {
+        let table = report().to_string();
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].trim_end(), "KEY           SOURCE   VALUE");
+        assert_eq!(lines[1], "PORT          default  8080");
+        assert_eq!(lines[2], "DATABASE_URL  .env:3   postgresql://localhost/db");
+    }
+}
@@ -0,0 +1,74 @@
+use crate::directory::read_trimmed;
+use std::path::Path;
+
+/// Turns a secret reference into the secret it points to
+///
+/// Register resolvers with [`ConfigBuilder::with_resolver`](crate::ConfigBuilder::with_resolver).
+/// A value like `secretref://prod/db#password` is passed to the resolver
+/// registered for `secretref` as `prod/db#password`, and the secret it
+/// returns is parsed in place of the value. Values with a scheme nobody
+/// registered are used as they are.
+///
+/// The error is shown as the reason the reference couldn't be resolved, so it
+/// must never contain the secret. Closures taking the reference can be used
+/// as resolvers, which is handy for tests.
+///
+/// # Example
+/// ```rust
+/// use config_loadr::{ConfigBuilder, FileResolver};
+///
+/// let builder = ConfigBuilder::new()
+///     .with_resolver("file", FileResolver)
+///     .with_resolver("secretref", |reference: &str| match reference {
+///         "prod/db#password" => Ok("hunter2".to_string()),
+///         _ => Err(format!("no secret at {}", reference)),
+///     });
+/// ```
+pub trait SecretResolver {
+    /// Look up the secret a reference points to, without its `scheme://`
+    fn resolve(&self, reference: &str) -> Result<String, String>;
+}
+
+impl<F: Fn(&str) -> Result<String, String>> SecretResolver for F {
+    fn resolve(&self, reference: &str) -> Result<String, String> {
+        self(reference)
+    }
+}
+
+/// Resolves `file://` references by reading the file they name
+///
+/// `file:///run/secrets/db_password` reads `/run/secrets/db_password`. The
+/// content is trimmed, like files read for `KEY_FILE` variables.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileResolver;
+
+impl SecretResolver for FileResolver {
+    fn resolve(&self, reference: &str) -> Result<String, String> {
+        let path = Path::new(reference);
+        read_trimmed(path).map_err(|e| format!("could not read {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use std::fs;
+
+    #[test]
+    fn test_file_resolver_trims_content() {
+        let dir = temp_dir("resolve", &[("secret.txt", b"hunter2\n")]);
+
+        let resolved = FileResolver.resolve(dir.join("secret.txt").to_str().unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(resolved, Ok("hunter2".to_string()));
+    }
+
+    #[test]
+    fn test_file_resolver_reports_path() {
+        let error = FileResolver
+            .resolve("/nonexistent/config-loadr/secret")
+            .unwrap_err();
+        assert!(error.starts_with("could not read /nonexistent/config-loadr/secret: "));
+    }
+}
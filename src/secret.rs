@@ -0,0 +1,250 @@
+//! Values that must not end up in logs
+
+use std::{fmt, str::FromStr};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+/// What secret values are replaced with in errors, docs and provenance
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Drop a secret's raw value, overwriting it first with the `zeroize` feature
+pub(crate) fn wipe(value: String) {
+    #[cfg(feature = "zeroize")]
+    {
+        let mut value = value;
+        value.zeroize();
+    }
+    #[cfg(not(feature = "zeroize"))]
+    drop(value);
+}
+
+/// A value that is redacted wherever it is printed
+///
+/// `Debug` and `Display` print [`REDACTED`], so a config holding secrets can
+/// be logged with `{:?}`. The value itself is only reachable through
+/// [`Secret::expose`]. Fields of this type are treated as `#[field(secret)]`,
+/// and are parsed through the inner type's `FromStr`.
+///
+/// To also overwrite the value when it's dropped, use `ZeroizingSecret` from
+/// the `zeroize` feature.
+///
+/// # Example
+/// ```rust
+/// use config_loadr::Secret;
+///
+/// let key: Secret<String> = "hunter2".parse().unwrap();
+/// assert_eq!(format!("{:?}", key), "[REDACTED]");
+/// assert_eq!(key.expose(), "hunter2");
+/// ```
+#[derive(Clone, Default)]
+pub struct Secret<T>(T);
+
+impl<T> Secret<T> {
+    /// Wrap a value
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// The secret value
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap the secret value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: FromStr> FromStr for Secret<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+impl<T> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl<T> fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// Serialized as [`REDACTED`], so config dumps don't contain the value
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Secret<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Secret<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+/// A [`Secret`] that overwrites its value when dropped
+///
+/// Needs the `zeroize` feature, and a `T` implementing `zeroize::Zeroize`
+/// (`String`, `Vec<u8>` and the integer types all do), so the value doesn't
+/// linger in freed memory. Otherwise it behaves like [`Secret`], and fields of
+/// this type are treated as `#[field(secret)]` too.
+///
+/// # Example
+/// ```rust
+/// use config_loadr::ZeroizingSecret;
+///
+/// let key: ZeroizingSecret<String> = "hunter2".parse().unwrap();
+/// assert_eq!(format!("{:?}", key), "[REDACTED]");
+/// assert_eq!(key.expose(), "hunter2");
+/// ```
+#[cfg(feature = "zeroize")]
+#[derive(Clone, Default)]
+pub struct ZeroizingSecret<T: Zeroize>(T);
+
+#[cfg(feature = "zeroize")]
+impl<T: Zeroize> ZeroizingSecret<T> {
+    /// Wrap a value
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// The secret value
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    /// Unwrap the secret value, leaving a default behind to be wiped
+    pub fn into_inner(mut self) -> T
+    where
+        T: Default,
+    {
+        std::mem::take(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: Zeroize> Drop for ZeroizingSecret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: Zeroize> From<T> for ZeroizingSecret<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: Zeroize + FromStr> FromStr for ZeroizingSecret<T> {
+    type Err = T::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Self)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: Zeroize> fmt::Debug for ZeroizingSecret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: Zeroize> fmt::Display for ZeroizingSecret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// Serialized as [`REDACTED`], so config dumps don't contain the value
+#[cfg(all(feature = "zeroize", feature = "serde"))]
+impl<T: Zeroize> serde::Serialize for ZeroizingSecret<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(REDACTED)
+    }
+}
+
+#[cfg(all(feature = "zeroize", feature = "serde"))]
+impl<'de, T: Zeroize + serde::Deserialize<'de>> serde::Deserialize<'de> for ZeroizingSecret<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redacted_when_printed() {
+        let secret = Secret::new("hunter2".to_string());
+        assert_eq!(format!("{:?}", secret), REDACTED);
+        assert_eq!(secret.to_string(), REDACTED);
+        assert_eq!(format!("{:?}", Some(secret.clone())), "Some([REDACTED])");
+        assert_eq!(secret.into_inner(), "hunter2");
+    }
+
+    #[test]
+    fn test_parses_inner_type() {
+        let port: Secret<u16> = "8080".parse().unwrap();
+        assert_eq!(*port.expose(), 8080);
+        assert!("http".parse::<Secret<u16>>().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serialized_redacted() {
+        let secret = Secret::new("hunter2".to_string());
+        assert_eq!(serde_json::to_string(&secret).unwrap(), r#""[REDACTED]""#);
+
+        let secret: Secret<String> = serde_json::from_str(r#""hunter2""#).unwrap();
+        assert_eq!(secret.expose(), "hunter2");
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroizing_secret_wipes_on_drop() {
+        use std::{cell::Cell, rc::Rc};
+
+        struct Probe(Rc<Cell<bool>>);
+
+        impl Zeroize for Probe {
+            fn zeroize(&mut self) {
+                self.0.set(true);
+            }
+        }
+
+        let wiped = Rc::new(Cell::new(false));
+        let secret = ZeroizingSecret::new(Probe(wiped.clone()));
+        assert!(!wiped.get());
+        drop(secret);
+        assert!(wiped.get());
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_zeroizing_secret_string() {
+        let secret: ZeroizingSecret<String> = "hunter2".parse().unwrap();
+        assert_eq!(format!("{:?}", secret), REDACTED);
+        assert_eq!(secret.clone().into_inner(), "hunter2");
+        assert_eq!(secret.expose(), "hunter2");
+    }
+}
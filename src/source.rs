@@ -0,0 +1,162 @@
+use crate::provenance::Provenance;
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    path::PathBuf,
+};
+
+/// A place configuration values are read from
+///
+/// `ConfigBuilder` reads every field through a `Source`. The default is
+/// [`EnvSource`], which reads the process environment, but anything that can
+/// look up a value by key (a map, a file, a secrets directory) can be used.
+pub trait Source {
+    /// Look up the raw value for a key, returning None if it is not set
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Look up the raw value for a key, reporting values that exist but can't be read
+    ///
+    /// `ConfigBuilder` reads through this method. The default never fails,
+    /// sources backed by files override it so read errors aren't mistaken for
+    /// missing values.
+    fn try_get(&self, key: &str) -> Result<Option<String>, SourceError> {
+        Ok(self.get(key))
+    }
+
+    /// List every key this source currently knows about
+    fn keys(&self) -> Vec<String>;
+
+    /// Describe where the value for a key comes from
+    fn provenance(&self, _key: &str) -> Provenance {
+        Provenance::Source
+    }
+}
+
+impl<S: Source + ?Sized> Source for &S {
+    fn get(&self, key: &str) -> Option<String> {
+        (**self).get(key)
+    }
+
+    fn try_get(&self, key: &str) -> Result<Option<String>, SourceError> {
+        (**self).try_get(key)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        (**self).keys()
+    }
+
+    fn provenance(&self, key: &str) -> Provenance {
+        (**self).provenance(key)
+    }
+}
+
+/// A value exists in a source but could not be read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceError {
+    /// Path of the file holding the value
+    pub path: PathBuf,
+    /// Why the value could not be read
+    pub message: String,
+}
+
+/// Where an additional source sits relative to the builder's primary source
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precedence {
+    /// Consulted before the primary source, so its values win
+    Override,
+    /// Consulted only for keys the primary source doesn't have
+    Fallback,
+}
+
+/// Reads values from the process environment
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EnvSource;
+
+impl Source for EnvSource {
+    fn get(&self, key: &str) -> Option<String> {
+        env::var(key).ok()
+    }
+
+    fn keys(&self) -> Vec<String> {
+        env::vars_os()
+            .filter_map(|(key, _)| key.into_string().ok())
+            .collect()
+    }
+
+    fn provenance(&self, _key: &str) -> Provenance {
+        Provenance::Env
+    }
+}
+
+/// Reads values from an in-memory map, useful for tests
+impl Source for HashMap<String, String> {
+    fn get(&self, key: &str) -> Option<String> {
+        HashMap::get(self, key).cloned()
+    }
+
+    fn keys(&self) -> Vec<String> {
+        HashMap::keys(self).cloned().collect()
+    }
+}
+
+/// Reads values from an in-memory map, useful for tests
+impl Source for BTreeMap<String, String> {
+    fn get(&self, key: &str) -> Option<String> {
+        BTreeMap::get(self, key).cloned()
+    }
+
+    fn keys(&self) -> Vec<String> {
+        BTreeMap::keys(self).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::PairSource;
+
+    #[test]
+    fn test_env_source_reads_process_env() {
+        let source = EnvSource;
+        // PATH is set in every environment the tests run in
+        assert!(source.get("PATH").is_some());
+        assert!(source.keys().iter().any(|k| k == "PATH"));
+        assert_eq!(source.provenance("PATH"), Provenance::Env);
+    }
+
+    #[test]
+    fn test_env_source_missing_key() {
+        let source = EnvSource;
+        assert_eq!(source.get("CONFIG_LOADR_SOURCE_TEST_UNSET"), None);
+    }
+
+    #[test]
+    fn test_hash_map_source() {
+        let map = HashMap::from([("PORT".to_string(), "8080".to_string())]);
+
+        assert_eq!(Source::get(&map, "PORT"), Some("8080".to_string()));
+        assert_eq!(Source::get(&map, "HOST"), None);
+        assert_eq!(Source::keys(&map), vec!["PORT".to_string()]);
+    }
+
+    #[test]
+    fn test_btree_map_source() {
+        let map = BTreeMap::from([
+            ("B".to_string(), "2".to_string()),
+            ("A".to_string(), "1".to_string()),
+        ]);
+
+        assert_eq!(Source::get(&map, "A"), Some("1".to_string()));
+        assert_eq!(Source::keys(&map), vec!["A".to_string(), "B".to_string()]);
+    }
+
+    #[test]
+    fn test_reference_delegates() {
+        let source = PairSource(&[("PORT", "8080")]);
+        let by_ref: &dyn Source = &source;
+
+        assert_eq!((&by_ref).get("PORT"), Some("8080".to_string()));
+        assert_eq!((&by_ref).keys(), vec!["PORT".to_string()]);
+        assert_eq!((&by_ref).get("HOST"), None);
+    }
+}
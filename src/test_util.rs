@@ -0,0 +1,36 @@
+//! Fixtures shared by the unit tests
+
+use crate::Source;
+use std::{fs, path::PathBuf};
+
+/// A source over a fixed list of key/value pairs
+pub(crate) struct PairSource(pub(crate) &'static [(&'static str, &'static str)]);
+
+impl Source for PairSource {
+    fn get(&self, key: &str) -> Option<String> {
+        self.0
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.to_string())
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.0.iter().map(|(k, _)| k.to_string()).collect()
+    }
+}
+
+/// Creates a fresh directory in the system temp dir holding `files`, unique per
+/// test name and process
+pub(crate) fn temp_dir(name: &str, files: &[(&str, &[u8])]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "config-loadr-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for (name, content) in files {
+        fs::write(dir.join(name), content).unwrap();
+    }
+    dir
+}
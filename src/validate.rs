@@ -0,0 +1,190 @@
+//! Constraints checked on field values after they are parsed
+//!
+//! Each function returns the reason a value is rejected, as passed to
+//! [`ConfigBuilder::check`](crate::ConfigBuilder::check). These are the checks
+//! `define_config!` generates for `range`, `min_len`, `max_len`, `one_of` and
+//! `matches`.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt,
+    ops::RangeBounds,
+};
+
+/// Values with a length, for [`min_len`] and [`max_len`]
+///
+/// Strings are measured in characters, collections in elements.
+pub trait Length {
+    /// The length of the value
+    fn length(&self) -> usize;
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl<T> Length for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, S> Length for HashSet<T, S> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for BTreeSet<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, S> Length for HashMap<K, V, S> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Length for BTreeMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+/// The value must be within `range`, like `1..=65535`
+pub fn range<T, R>(value: &T, range: &R) -> Result<(), String>
+where
+    T: PartialOrd,
+    R: RangeBounds<T> + fmt::Debug,
+{
+    if range.contains(value) {
+        Ok(())
+    } else {
+        Err(format!("must be in range {:?}", range))
+    }
+}
+
+/// The value must have at least `min` characters or elements
+pub fn min_len<T: Length + ?Sized>(value: &T, min: usize) -> Result<(), String> {
+    let length = value.length();
+    if length >= min {
+        Ok(())
+    } else {
+        Err(format!(
+            "must have a length of at least {}, got {}",
+            min, length
+        ))
+    }
+}
+
+/// The value must have at most `max` characters or elements
+pub fn max_len<T: Length + ?Sized>(value: &T, max: usize) -> Result<(), String> {
+    let length = value.length();
+    if length <= max {
+        Ok(())
+    } else {
+        Err(format!(
+            "must have a length of at most {}, got {}",
+            max, length
+        ))
+    }
+}
+
+/// The value must equal one of `allowed`
+pub fn one_of<T, A>(value: &T, allowed: &[A]) -> Result<(), String>
+where
+    T: PartialEq<A>,
+    A: fmt::Debug,
+{
+    if allowed.iter().any(|candidate| value == candidate) {
+        Ok(())
+    } else {
+        Err(format!("must be one of {:?}", allowed))
+    }
+}
+
+/// The whole value must match the regular expression `pattern`
+#[cfg(feature = "regex")]
+pub fn matches<T: AsRef<str> + ?Sized>(value: &T, pattern: &str) -> Result<(), String> {
+    let regex = regex::Regex::new(&format!("^(?:{})$", pattern))
+        .map_err(|e| format!("invalid pattern `{}`: {}", pattern, e))?;
+    if regex.is_match(value.as_ref()) {
+        Ok(())
+    } else {
+        Err(format!("must match `{}`", pattern))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range() {
+        assert_eq!(range(&443u16, &(1..=65535)), Ok(()));
+        assert_eq!(
+            range(&0u16, &(1..=65535)),
+            Err("must be in range 1..=65535".to_string())
+        );
+        assert_eq!(range(&0.5f64, &(0.0..1.0)), Ok(()));
+        assert!(range(&1.0f64, &(0.0..1.0)).is_err());
+    }
+
+    #[test]
+    fn test_length() {
+        assert_eq!(
+            min_len("", 1),
+            Err("must have a length of at least 1, got 0".to_string())
+        );
+        assert_eq!(min_len("é", 1), Ok(()));
+        assert_eq!(
+            max_len(&vec![1, 2, 3], 2),
+            Err("must have a length of at most 2, got 3".to_string())
+        );
+        assert_eq!(max_len(&HashMap::<String, u8>::new(), 2), Ok(()));
+    }
+
+    #[test]
+    fn test_one_of() {
+        assert_eq!(one_of(&"info".to_string(), &["debug", "info"]), Ok(()));
+        assert_eq!(
+            one_of(&"trace".to_string(), &["debug", "info"]),
+            Err(r#"must be one of ["debug", "info"]"#.to_string())
+        );
+        assert_eq!(
+            one_of(&3u8, &[1, 2]),
+            Err("must be one of [1, 2]".to_string())
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_matches_whole_value() {
+        assert_eq!(matches("eu-west-1", "[a-z]+-[a-z]+-[0-9]"), Ok(()));
+        assert_eq!(
+            matches("eu-west-12", "[a-z]+-[a-z]+-[0-9]"),
+            Err("must match `[a-z]+-[a-z]+-[0-9]`".to_string())
+        );
+        assert!(
+            matches("x", "(")
+                .unwrap_err()
+                .starts_with("invalid pattern `(`")
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use colored::Colorize;
+use std::fmt;
+
+/// Problems found while loading configuration that don't stop it from loading
+///
+/// Collected by `ConfigBuilder` next to errors, and returned by the generated
+/// `new_with_warnings()`. A builder in strict mode reports every warning as
+/// [`ConfigError::Warning`](crate::ConfigError::Warning) instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigWarning {
+    /// A value was read from a deprecated alias of its key
+    DeprecatedAlias {
+        /// The deprecated key that is set
+        alias: String,
+        /// The key to use instead
+        key: String,
+    },
+    /// A field uses its default while running in production
+    DefaultInProduction {
+        key: String,
+        /// The default value, `[REDACTED]` for secret fields
+        default: String,
+        description: String,
+    },
+    /// A variable starts with a config's prefix but no field reads it
+    UnknownKey {
+        key: String,
+        /// The prefix it starts with, like `BILLING_`
+        prefix: String,
+    },
+}
+
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigWarning::DeprecatedAlias { alias, key } => {
+                writeln!(
+                    f,
+                    "{}: Is deprecated, use {} instead",
+                    alias.magenta().bold(),
+                    key.magenta().bold()
+                )
+            }
+            ConfigWarning::DefaultInProduction {
+                key,
+                default,
+                description,
+            } => {
+                writeln!(
+                    f,
+                    "{}: Is not set, using the default {} in production",
+                    key.magenta().bold(),
+                    format!("'{}'", default).yellow()
+                )?;
+                writeln!(f, "\tDescription: {}", description)
+            }
+            ConfigWarning::UnknownKey { key, prefix } => {
+                writeln!(
+                    f,
+                    "{}: Starts with {} but is not a known variable",
+                    key.magenta().bold(),
+                    prefix.yellow()
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deprecated_alias_display() {
+        colored::control::set_override(false);
+
+        let warning = ConfigWarning::DeprecatedAlias {
+            alias: "DB_URL".to_string(),
+            key: "DATABASE_URL".to_string(),
+        };
+        assert_eq!(
+            warning.to_string(),
+            "DB_URL: Is deprecated, use DATABASE_URL instead\n"
+        );
+    }
+
+    #[test]
+    fn test_default_in_production_display() {
+        colored::control::set_override(false);
+
+        let warning = ConfigWarning::DefaultInProduction {
+            key: "LOG_LEVEL".to_string(),
+            default: "debug".to_string(),
+            description: "Log level".to_string(),
+        };
+        let output = warning.to_string();
+        assert!(output.contains("LOG_LEVEL: Is not set, using the default 'debug' in production"));
+        assert!(output.contains("Description: Log level"));
+    }
+
+    #[test]
+    fn test_unknown_key_display() {
+        colored::control::set_override(false);
+
+        let warning = ConfigWarning::UnknownKey {
+            key: "BILLING_PROT".to_string(),
+            prefix: "BILLING_".to_string(),
+        };
+        assert_eq!(
+            warning.to_string(),
+            "BILLING_PROT: Starts with BILLING_ but is not a known variable\n"
+        );
+    }
+}
@@ -0,0 +1,50 @@
+//! Fixtures shared by the integration tests
+#![allow(dead_code)]
+
+use config_loadr::Source;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A source over a fixed list of key/value pairs
+pub struct PairSource(pub &'static [(&'static str, &'static str)]);
+
+impl Source for PairSource {
+    fn get(&self, key: &str) -> Option<String> {
+        self.0
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v.to_string())
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.0.iter().map(|(k, _)| k.to_string()).collect()
+    }
+}
+
+/// Creates a fresh directory in the system temp dir holding `files`, unique per
+/// test name and process
+pub fn temp_dir(name: &str, files: &[(&str, &[u8])]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "config-loadr-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for (name, content) in files {
+        fs::write(dir.join(name), content).unwrap();
+    }
+    dir
+}
+
+/// Creates a single file named `name` in its own temp directory
+pub fn temp_file(name: &str, content: &[u8]) -> PathBuf {
+    temp_dir(name, &[(name, content)]).join(name)
+}
+
+/// Removes a file created by [`temp_file`] along with its directory
+pub fn remove_temp_file(path: &Path) {
+    fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,102 @@
+use config_loadr::{ConfigBuilder, ConfigError, Load, Provenance, define_config};
+use std::collections::HashMap;
+
+define_config! {
+    #[derive(Debug)]
+    pub struct AliasConfig {
+        #[field(env = "ALIAS_DATABASE_URL", doc = "Database URL", example = "postgres://localhost/db".to_string(), required, aliases = ["ALIAS_DB_URL", "ALIAS_DB"])]
+        pub database_url: String,
+
+        #[field(env = "PORT", doc = "Server port", default = 8080u16, aliases = ["HTTP_PORT"])]
+        pub port: u16,
+    }
+}
+
+define_config! {
+    #[config(prefix = "ALIAS_BILLING_")]
+    pub struct PrefixedAliasConfig {
+        #[field(env = "PORT", doc = "Server port", default = 8080u16, aliases = ["HTTP_PORT"])]
+        pub port: u16,
+    }
+}
+
+#[test]
+fn test_alias_is_read_when_key_is_missing() {
+    let config = AliasConfig::from_iter([("ALIAS_DB_URL", "postgres://old/db")]).unwrap();
+    assert_eq!(config.database_url, "postgres://old/db");
+
+    let config = AliasConfig::from_iter([("ALIAS_DB", "postgres://older/db")]).unwrap();
+    assert_eq!(config.database_url, "postgres://older/db");
+}
+
+#[test]
+fn test_same_value_under_both_names_is_allowed() {
+    let config = AliasConfig::from_iter([
+        ("ALIAS_DATABASE_URL", "postgres://new/db"),
+        ("ALIAS_DB_URL", "postgres://new/db"),
+    ])
+    .unwrap();
+    assert_eq!(config.database_url, "postgres://new/db");
+}
+
+#[test]
+fn test_conflicting_values_are_an_error() {
+    let errors = AliasConfig::from_iter([
+        ("ALIAS_DATABASE_URL", "postgres://new/db"),
+        ("ALIAS_DB_URL", "postgres://new/db"),
+        ("ALIAS_DB", "postgres://old/db"),
+    ])
+    .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::ConflictingAliases { key, set, .. }
+            if key == "ALIAS_DATABASE_URL"
+                && set == &["ALIAS_DATABASE_URL", "ALIAS_DB_URL", "ALIAS_DB"]
+    ));
+}
+
+#[test]
+fn test_aliases_are_prefixed() {
+    let config = PrefixedAliasConfig::from_iter([("ALIAS_BILLING_HTTP_PORT", "9000")]).unwrap();
+    assert_eq!(config.port, 9000);
+}
+
+#[test]
+fn test_alias_provenance() {
+    let source: HashMap<String, String> = [
+        ("ALIAS_DB_URL".to_string(), "postgres://old/db".to_string()),
+        ("HTTP_PORT".to_string(), "9000".to_string()),
+    ]
+    .into();
+    let mut builder = ConfigBuilder::with_source(&source);
+    AliasConfig::load_into(&mut builder).unwrap();
+
+    let report = builder.provenance();
+    assert_eq!(
+        report.get("ALIAS_DATABASE_URL").unwrap().provenance,
+        Provenance::Alias {
+            alias: "ALIAS_DB_URL".to_string(),
+            provenance: Box::new(Provenance::Source),
+        }
+    );
+    assert!(
+        report
+            .to_string()
+            .contains("custom source (deprecated HTTP_PORT)")
+    );
+}
+
+#[test]
+fn test_docs_list_deprecated_names() {
+    let path = std::env::temp_dir().join(format!("config-loadr-aliases-{}.md", std::process::id()));
+    AliasConfig::builder_for_docs().write_docs(&path).unwrap();
+    let docs = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(docs.contains("## Deprecated Variables"));
+    assert!(docs.contains("- `ALIAS_DB_URL`: use `ALIAS_DATABASE_URL` instead"));
+    assert!(docs.contains("- `ALIAS_DB`: use `ALIAS_DATABASE_URL` instead"));
+    assert!(docs.contains("- `HTTP_PORT`: use `PORT` instead"));
+}
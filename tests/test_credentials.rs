@@ -0,0 +1,140 @@
+mod common;
+
+use config_loadr::{ConfigBuilder, ConfigError, FieldOptions, Load, Provenance, define_config};
+use common::temp_dir;
+use std::{collections::HashMap, fs};
+
+define_config! {
+    #[derive(Debug)]
+    pub struct ServiceConfig {
+        #[field(env = "SERVICE_DB_PASSWORD", doc = "Database password", example = "hunter2".to_string(), required, credential = "db_password")]
+        pub db_password: String,
+
+        #[field(env = "SERVICE_API_TOKEN", doc = "API token", optional, credential)]
+        pub api_token: Option<String>,
+    }
+}
+
+#[test]
+fn test_credential_wins_over_env() {
+    let dir = temp_dir(
+        "credentials-wins",
+        &[
+            ("db_password", b"from-credential\n"),
+            ("SERVICE_API_TOKEN", b"token"),
+        ],
+    );
+
+    let config = ServiceConfig::from_iter([
+        ("CREDENTIALS_DIRECTORY", dir.to_str().unwrap()),
+        ("SERVICE_DB_PASSWORD", "from-env"),
+    ])
+    .unwrap();
+    assert_eq!(config.db_password, "from-credential");
+    assert_eq!(config.api_token.as_deref(), Some("token"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_falls_back_to_env() {
+    let dir = temp_dir("credentials-fallback", &[]);
+
+    let config = ServiceConfig::from_iter([
+        ("CREDENTIALS_DIRECTORY", dir.to_str().unwrap()),
+        ("SERVICE_DB_PASSWORD", "from-env"),
+    ])
+    .unwrap();
+    assert_eq!(config.db_password, "from-env");
+    assert_eq!(config.api_token, None);
+
+    let config = ServiceConfig::from_iter([("SERVICE_DB_PASSWORD", "no-directory")]).unwrap();
+    assert_eq!(config.db_password, "no-directory");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_missing_credential_error() {
+    let dir = temp_dir("credentials-missing", &[]);
+
+    let errors =
+        ServiceConfig::from_iter([("CREDENTIALS_DIRECTORY", dir.to_str().unwrap())]).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::MissingCredential { key, name, directory, .. }
+            if key == "SERVICE_DB_PASSWORD" && name == "db_password" && directory.as_ref() == Some(&dir)
+    ));
+
+    let errors = ServiceConfig::from_iter([("UNRELATED", "x")]).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        ConfigError::MissingCredential {
+            directory: None,
+            ..
+        }
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_unreadable_credential_error() {
+    let dir = temp_dir("credentials-unreadable", &[("db_password", &[0xff, 0xfe])]);
+
+    let errors = ServiceConfig::from_iter([
+        ("CREDENTIALS_DIRECTORY", dir.to_str().unwrap()),
+        ("SERVICE_DB_PASSWORD", "from-env"),
+    ])
+    .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::UnreadableFile { key, path, .. }
+            if key == "SERVICE_DB_PASSWORD" && *path == dir.join("db_password")
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_credential_provenance() {
+    let dir = temp_dir("credentials-provenance", &[("db_password", b"abc")]);
+
+    let source = HashMap::from([(
+        "CREDENTIALS_DIRECTORY".to_string(),
+        dir.to_str().unwrap().to_string(),
+    )]);
+    let mut builder = ConfigBuilder::with_source(&source);
+    ServiceConfig::load_into(&mut builder).unwrap();
+    assert_eq!(
+        builder.provenance().get("db_password").unwrap().provenance,
+        Provenance::Credential {
+            path: dir.join("db_password")
+        }
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_docs_show_credential_precedence() {
+    let dir = temp_dir("credentials-docs", &[]);
+    let docs = dir.join("CONFIG.md");
+
+    let mut builder = ConfigBuilder::with_source(std::collections::HashMap::new());
+    let _ = builder.optional_with::<String>(
+        "SERVICE_DB_PASSWORD",
+        "Database password",
+        None,
+        &FieldOptions::new().credential("db_password"),
+    );
+    builder.write_docs(&docs).unwrap();
+
+    let content = fs::read_to_string(&docs).unwrap();
+    assert!(content.contains("| $CREDENTIALS_DIRECTORY/db_password, then SERVICE_DB_PASSWORD |"));
+    assert!(content.contains("LoadCredential="));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
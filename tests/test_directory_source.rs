@@ -0,0 +1,105 @@
+mod common;
+
+use config_loadr::{
+    ConfigBuilder, ConfigError, DirectorySource, Load, Precedence, Provenance, define_config,
+};
+use common::temp_dir;
+use std::{collections::HashMap, fs};
+
+define_config! {
+    #[derive(Debug)]
+    pub struct MountedConfig {
+        #[field(env = "MOUNT_DB_PASSWORD", doc = "Database password", example = "hunter2".to_string(), required)]
+        pub db_password: String,
+
+        #[field(env = "MOUNT_DB_PORT", doc = "Database port", default = 5432u16)]
+        pub db_port: u16,
+    }
+}
+
+fn env(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_directory_as_fallback() {
+    let dir = temp_dir(
+        "mount-fallback",
+        &[
+            ("MOUNT_DB_PASSWORD", b"from-mount\n"),
+            ("MOUNT_DB_PORT", b"6543"),
+        ],
+    );
+    let env = env(&[("MOUNT_DB_PORT", "7000")]);
+
+    let config = MountedConfig::new_with_builder(
+        ConfigBuilder::with_source(&env)
+            .with_layer(DirectorySource::new(&dir), Precedence::Fallback),
+    )
+    .unwrap();
+    assert_eq!(config.db_password, "from-mount");
+    assert_eq!(config.db_port, 7000);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_directory_as_override() {
+    let dir = temp_dir("mount-override", &[("MOUNT_DB_PORT", b"6543")]);
+    let env = env(&[("MOUNT_DB_PASSWORD", "from-env"), ("MOUNT_DB_PORT", "7000")]);
+
+    let config = MountedConfig::new_with_builder(
+        ConfigBuilder::with_source(&env)
+            .with_layer(DirectorySource::new(&dir), Precedence::Override),
+    )
+    .unwrap();
+    assert_eq!(config.db_password, "from-env");
+    assert_eq!(config.db_port, 6543);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_directory_errors_name_file() {
+    let dir = temp_dir(
+        "mount-errors",
+        &[
+            ("MOUNT_DB_PASSWORD", &[0xff, 0xfe]),
+            ("MOUNT_DB_PORT", b"not-a-port"),
+        ],
+    );
+
+    let errors = MountedConfig::new_from(&DirectorySource::new(&dir)).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::UnreadableFile { key, path, .. }
+            if key == "MOUNT_DB_PASSWORD" && *path == dir.join("MOUNT_DB_PASSWORD")
+    ));
+    assert!(matches!(
+        &errors[1],
+        ConfigError::InvalidEnvironment { key, .. } if key == "MOUNT_DB_PORT"
+    ));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_directory_provenance() {
+    let dir = temp_dir("mount-provenance", &[("MOUNT_DB_PASSWORD", b"abc")]);
+
+    let mut builder = ConfigBuilder::with_source(DirectorySource::new(&dir));
+    let config = MountedConfig::load_into(&mut builder).unwrap();
+    assert_eq!(config.db_password, "abc");
+    assert_eq!(
+        builder.provenance().get("db_password").unwrap().provenance,
+        Provenance::Directory {
+            path: dir.join("MOUNT_DB_PASSWORD")
+        }
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
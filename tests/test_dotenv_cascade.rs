@@ -0,0 +1,97 @@
+use config_loadr::{Environment, define_config};
+use std::path::PathBuf;
+
+define_config! {
+    #[derive(Debug)]
+    #[config(dotenv_cascade = "tests/fixtures/cascade")]
+    pub struct ProdCascadeConfig {
+        #[field(env = "DOTENV_CASCADE_PROD_ENVIRONMENT", doc = "Application environment", example = Environment::Prod, required)]
+        pub env: Environment,
+
+        #[field(env = "DOTENV_CASCADE_NAME", doc = "Set in every file", example = "x".to_string(), required)]
+        pub name: String,
+
+        #[field(env = "DOTENV_CASCADE_REGION", doc = "Set in .env and .env.prod", example = "x".to_string(), required)]
+        pub region: String,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(dotenv_cascade = "tests/fixtures/cascade", dotenv_override)]
+    pub struct DevCascadeConfig {
+        #[field(env = "DOTENV_CASCADE_DEV_ENVIRONMENT", doc = "Application environment", default = Environment::Dev)]
+        pub env: Environment,
+
+        #[field(env = "DOTENV_CASCADE_NAME", doc = "Set in every file", example = "x".to_string(), required)]
+        pub name: String,
+
+        #[field(env = "DOTENV_CASCADE_REGION", doc = "Set in .env and .env.prod", example = "x".to_string(), required)]
+        pub region: String,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(dotenv_cascade = "tests/fixtures/cascade-named")]
+    pub struct NamedCascadeConfig {
+        #[field(env = "DOTENV_CASCADE_NAMED_ENVIRONMENT", doc = "Set in .env", default = Environment::Dev)]
+        pub env: Environment,
+
+        #[field(env = "DOTENV_CASCADE_NAMED_REGION", doc = "Set in .env and .env.prod", example = "x".to_string(), required)]
+        pub region: String,
+    }
+}
+
+fn fixture(name: &str) -> PathBuf {
+    std::env::current_dir()
+        .unwrap()
+        .join("tests/fixtures/cascade")
+        .join(name)
+}
+
+// Both configs load the same variables into the process environment, so they
+// run in order in a single test
+#[test]
+fn test_cascade_follows_environment() {
+    std::env::set_var("DOTENV_CASCADE_PROD_ENVIRONMENT", "prod");
+
+    let (config, report) = ProdCascadeConfig::new_with_provenance().unwrap();
+    assert_eq!(config.env, Environment::Prod);
+    assert_eq!(config.name, "prod-local");
+    assert_eq!(config.region, "eu");
+    assert_eq!(
+        report.dotenv_files(),
+        [
+            fixture(".env.prod.local"),
+            fixture(".env.prod"),
+            fixture(".env.local"),
+            fixture(".env"),
+        ]
+    );
+
+    // Falls back to the field default, and there is no .env.dev
+    let (config, report) = DevCascadeConfig::new_with_provenance().unwrap();
+    assert_eq!(config.env, Environment::Dev);
+    assert_eq!(config.name, "local");
+    assert_eq!(config.region, "base");
+    assert_eq!(
+        report.dotenv_files(),
+        [fixture(".env"), fixture(".env.local")]
+    );
+}
+
+#[test]
+fn test_cascade_environment_from_base_dotenv() {
+    let (config, report) = NamedCascadeConfig::new_with_provenance().unwrap();
+    assert_eq!(config.env, Environment::Prod);
+    assert_eq!(config.region, "eu");
+
+    let dir = std::env::current_dir()
+        .unwrap()
+        .join("tests/fixtures/cascade-named");
+    assert_eq!(
+        report.dotenv_files(),
+        [dir.join(".env.prod"), dir.join(".env")]
+    );
+}
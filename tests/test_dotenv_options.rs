@@ -0,0 +1,73 @@
+use config_loadr::{ConfigError, define_config};
+
+define_config! {
+    #[derive(Debug)]
+    #[config(dotenv = ["tests/fixtures/local.env", "tests/fixtures/base.env"])]
+    pub struct OrderedDotenvConfig {
+        #[field(env = "DOTENV_OPTIONS_SHARED", doc = "Set in both files", example = "x".to_string(), required)]
+        pub shared: String,
+
+        #[field(env = "DOTENV_OPTIONS_BASE_ONLY", doc = "Only set in the base file", example = "x".to_string(), required)]
+        pub base_only: String,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(dotenv = "tests/fixtures/override.env", dotenv_override)]
+    pub struct OverrideDotenvConfig {
+        #[field(env = "DOTENV_OPTIONS_OVERRIDE", doc = "Overridden by the file", example = "x".to_string(), required)]
+        pub value: String,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(dotenv = "tests/fixtures/malformed.env")]
+    pub struct MalformedDotenvConfig {
+        #[field(env = "DOTENV_OPTIONS_MALFORMED_PORT", doc = "Port", default = 8080u16)]
+        pub port: u16,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(dotenv = false)]
+    pub struct NoDotenvConfig {
+        #[field(env = "DOTENV_OPTIONS_DISABLED", doc = "Never set by a file", optional)]
+        pub value: Option<String>,
+    }
+}
+
+#[test]
+fn test_earlier_files_win() {
+    let config = OrderedDotenvConfig::new().unwrap();
+    assert_eq!(config.shared, "local");
+    assert_eq!(config.base_only, "base");
+}
+
+#[test]
+fn test_override_replaces_environment() {
+    std::env::set_var("DOTENV_OPTIONS_OVERRIDE", "from-env");
+
+    let config = OverrideDotenvConfig::new().unwrap();
+    assert_eq!(config.value, "from-file");
+}
+
+#[test]
+fn test_malformed_file_is_reported() {
+    let errors = MalformedDotenvConfig::new().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::FileError { path, .. } if path.ends_with("tests/fixtures/malformed.env")
+    ));
+}
+
+#[test]
+fn test_disabled_dotenv_still_loads_environment() {
+    std::env::set_var("DOTENV_OPTIONS_DISABLED", "from-env");
+
+    let config = NoDotenvConfig::new().unwrap();
+    assert_eq!(config.value.as_deref(), Some("from-env"));
+}
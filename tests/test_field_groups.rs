@@ -0,0 +1,90 @@
+use config_loadr::{ConfigError, define_config};
+
+define_config! {
+    #[derive(Debug)]
+    #[config(conflicts(database_url, db_host), at_least_one_of(database_url, db_host))]
+    pub struct DatabaseGroupConfig {
+        #[field(env = "FG_DATABASE_URL", doc = "Database URL", optional)]
+        pub database_url: Option<String>,
+
+        #[field(env = "FG_DB_HOST", doc = "Database host", optional)]
+        pub db_host: Option<String>,
+
+        #[field(env = "FG_DB_PORT", doc = "Database port", default = 5432u16)]
+        pub db_port: u16,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(exactly_one_of(api_token, api_token_file))]
+    pub struct TokenGroupConfig {
+        #[field(env = "FG_API_TOKEN", doc = "API token", optional)]
+        pub api_token: Option<String>,
+
+        #[field(env = "FG_API_TOKEN_PATH", doc = "File holding the API token", optional)]
+        pub api_token_file: Option<String>,
+    }
+}
+
+#[test]
+fn test_one_field_of_group_set() {
+    let config = DatabaseGroupConfig::from_iter([("FG_DB_HOST", "db")]).unwrap();
+    assert_eq!(config.db_host.as_deref(), Some("db"));
+
+    let config = TokenGroupConfig::from_iter([("FG_API_TOKEN", "abc")]).unwrap();
+    assert_eq!(config.api_token.as_deref(), Some("abc"));
+}
+
+#[test]
+fn test_conflicting_fields() {
+    let errors = DatabaseGroupConfig::from_iter([
+        ("FG_DATABASE_URL", "postgres://db/app"),
+        ("FG_DB_HOST", "db"),
+    ])
+    .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::ConflictingFields { set, group }
+            if set == &["FG_DATABASE_URL", "FG_DB_HOST"] && group == set
+    ));
+
+    let errors = TokenGroupConfig::from_iter([
+        ("FG_API_TOKEN", "abc"),
+        ("FG_API_TOKEN_PATH", "/run/secrets/token"),
+    ])
+    .unwrap_err();
+    assert!(matches!(&errors[0], ConfigError::ConflictingFields { .. }));
+}
+
+#[test]
+fn test_missing_group() {
+    let errors = DatabaseGroupConfig::from_iter([("FG_DB_PORT", "5433")]).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::MissingOneOf { group } if group == &["FG_DATABASE_URL", "FG_DB_HOST"]
+    ));
+
+    let errors = TokenGroupConfig::from_iter(Vec::<(&str, &str)>::new()).unwrap_err();
+    assert!(matches!(&errors[0], ConfigError::MissingOneOf { .. }));
+}
+
+#[test]
+fn test_docs_list_groups() {
+    let path = std::env::temp_dir().join(format!(
+        "config-loadr-field-groups-{}.md",
+        std::process::id()
+    ));
+    DatabaseGroupConfig::builder_for_docs()
+        .write_docs(&path)
+        .unwrap();
+    let docs = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(docs.contains("## Field Groups"));
+    assert!(docs.contains("- Only one of `FG_DATABASE_URL`, `FG_DB_HOST` can be set"));
+    assert!(docs.contains("- At least one of `FG_DATABASE_URL`, `FG_DB_HOST` must be set"));
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use config_loadr::{ConfigBuilder, ConfigError, Load, Provenance, define_config};
+use common::{remove_temp_file, temp_file};
+use std::collections::HashMap;
+
+define_config! {
+    #[derive(Debug)]
+    pub struct SecretConfig {
+        #[field(env = "DB_PASSWORD", doc = "Database password", example = "hunter2".to_string(), required, file_fallback)]
+        pub db_password: String,
+
+        #[field(env = "DB_PORT", doc = "Database port", default = 5432u16)]
+        pub db_port: u16,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(file_fallback)]
+    pub struct AllSecretsConfig {
+        #[field(env = "API_TOKEN", doc = "API token", optional)]
+        pub api_token: Option<String>,
+
+        #[field(env = "API_RETRIES", doc = "Retry count", default = 3u8)]
+        pub api_retries: u8,
+    }
+}
+
+#[test]
+fn test_reads_and_trims_file() {
+    let path = temp_file("file-fallback-token", b"s3cret\n");
+
+    let source = HashMap::from([(
+        "DB_PASSWORD_FILE".to_string(),
+        path.to_str().unwrap().to_string(),
+    )]);
+    let mut builder = ConfigBuilder::with_source(&source);
+    let config = SecretConfig::load_into(&mut builder);
+    remove_temp_file(&path);
+    assert_eq!(config.unwrap().db_password, "s3cret");
+
+    assert_eq!(
+        builder.provenance().get("DB_PASSWORD").unwrap().provenance,
+        Provenance::EnvFile {
+            key: "DB_PASSWORD_FILE".to_string(),
+            path
+        }
+    );
+}
+
+#[test]
+fn test_env_var_wins_over_file() {
+    let path = temp_file("file-fallback-db-password-unused", b"from-file");
+
+    let config = SecretConfig::from_iter([
+        ("DB_PASSWORD", "from-env"),
+        ("DB_PASSWORD_FILE", path.to_str().unwrap()),
+    ]);
+    remove_temp_file(&path);
+    assert_eq!(config.unwrap().db_password, "from-env");
+}
+
+#[test]
+fn test_unreadable_file_is_distinct_error() {
+    let errors =
+        SecretConfig::from_iter([("DB_PASSWORD_FILE", "/nonexistent/config-loadr/secret")])
+            .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::UnreadableFile { key, .. } if key == "DB_PASSWORD_FILE"
+    ));
+}
+
+#[test]
+fn test_only_marked_fields_use_file() {
+    let path = temp_file("file-fallback-db-port", b"6543");
+
+    let config = SecretConfig::from_iter([
+        ("DB_PASSWORD", "x"),
+        ("DB_PORT_FILE", path.to_str().unwrap()),
+    ]);
+    remove_temp_file(&path);
+    assert_eq!(config.unwrap().db_port, 5432);
+}
+
+#[test]
+fn test_struct_level_opt_in() {
+    let token = temp_file("file-fallback-api-token", b"  abc  \n");
+    let retries = temp_file("file-fallback-api-retries", b"not-a-number");
+
+    let config = AllSecretsConfig::from_iter([("API_TOKEN_FILE", token.to_str().unwrap())]);
+    let errors = AllSecretsConfig::from_iter([("API_RETRIES_FILE", retries.to_str().unwrap())]);
+    remove_temp_file(&token);
+    remove_temp_file(&retries);
+
+    assert_eq!(config.unwrap().api_token.as_deref(), Some("abc"));
+    let errors = errors.unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        ConfigError::InvalidEnvironment { key, .. } if key == "API_RETRIES"
+    ));
+}
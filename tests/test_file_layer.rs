@@ -0,0 +1,89 @@
+#![cfg(feature = "toml")]
+
+use config_loadr::{ConfigError, FileLayer, define_config};
+
+define_config! {
+    #[derive(Debug)]
+    pub struct FileConfig {
+        #[field(env = "FILE_TEST_URL", doc = "Service URL", example = "http://localhost".to_string(), required)]
+        pub url: String,
+
+        #[field(env = "FILE_TEST_PORT", doc = "Server port", default = 8080u16)]
+        pub port: u16,
+
+        #[field(env = "FILE_TEST_WORKERS", doc = "Worker count", optional)]
+        pub workers: Option<u32>,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    pub struct FileOverrideConfig {
+        #[field(env = "FILE_TEST_OVERRIDE_NAME", doc = "Service name", default = String::from("default"))]
+        pub name: String,
+    }
+}
+
+#[test]
+fn test_values_by_field_name_and_env_key() {
+    let file = FileLayer::toml_str(
+        "config.toml",
+        "url = \"http://file.example.com\"\nFILE_TEST_PORT = 9000\n",
+    );
+
+    let config = FileConfig::new_with_file(file).unwrap();
+    assert_eq!(config.url, "http://file.example.com");
+    assert_eq!(config.port, 9000);
+    assert_eq!(config.workers, None);
+}
+
+#[test]
+fn test_environment_overrides_file() {
+    // Only this test touches this variable
+    std::env::set_var("FILE_TEST_OVERRIDE_NAME", "from-env");
+
+    let file = FileLayer::toml_str("config.toml", "name = \"from-file\"");
+    let config = FileOverrideConfig::new_with_file(file).unwrap();
+    assert_eq!(config.name, "from-env");
+}
+
+#[test]
+fn test_wrong_type_points_at_file_and_key() {
+    let file = FileLayer::toml_str(
+        "config.toml",
+        "url = \"http://x\"\nport = \"eighty\"\nworkers = [1, 2]\n",
+    );
+
+    let errors = FileConfig::new_with_file(file).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::InvalidFileValue { key, value, .. } if key == "port" && value == "eighty"
+    ));
+    assert!(matches!(
+        &errors[1],
+        ConfigError::InvalidFileValue { key, value, .. } if key == "workers" && value == "<array>"
+    ));
+}
+
+#[test]
+fn test_strict_reports_unknown_keys() {
+    let file = FileLayer::toml_str("config.toml", "url = \"http://x\"\nprot = 9000\n").strict();
+
+    let errors = FileConfig::new_with_file(file).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::UnknownFileKey { key, .. } if key == "prot"
+    ));
+}
+
+#[test]
+fn test_unreadable_file_is_collected() {
+    let file = FileLayer::toml("/nonexistent/config-loadr/config.toml");
+
+    let errors = FileConfig::new_with_file(file).unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(&errors[0], ConfigError::FileError { .. }));
+    assert!(matches!(&errors[1], ConfigError::MissingEnvVar { .. }));
+}
@@ -0,0 +1,68 @@
+use config_loadr::{ConfigError, define_config};
+use std::collections::HashMap;
+
+define_config! {
+    #[derive(Debug)]
+    pub struct MapConfig {
+        #[field(env = "MAP_TEST_STRING", doc = "String value", example = "test".to_string(), required)]
+        pub string: String,
+
+        #[field(env = "MAP_TEST_INT", doc = "Integer value", default = 42i32)]
+        pub int: i32,
+
+        #[field(env = "MAP_TEST_OPTIONAL", doc = "Optional value", example = 123, optional)]
+        pub optional: Option<i32>,
+    }
+}
+
+#[test]
+fn test_from_iter_loads_values() {
+    let config = MapConfig::from_iter([
+        ("MAP_TEST_STRING", "hello"),
+        ("MAP_TEST_INT", "7"),
+        ("MAP_TEST_OPTIONAL", "9"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.string, "hello");
+    assert_eq!(config.int, 7);
+    assert_eq!(config.optional, Some(9));
+}
+
+#[test]
+fn test_from_iter_uses_defaults() {
+    let config = MapConfig::from_iter([("MAP_TEST_STRING", "hello")]).unwrap();
+
+    assert_eq!(config.int, 42);
+    assert_eq!(config.optional, None);
+}
+
+#[test]
+fn test_from_iter_collects_errors() {
+    let errors =
+        MapConfig::from_iter([("MAP_TEST_INT", "x"), ("MAP_TEST_OPTIONAL", "y")]).unwrap_err();
+
+    assert_eq!(errors.len(), 3);
+    assert!(
+        matches!(&errors[0], ConfigError::MissingEnvVar { key, .. } if key == "MAP_TEST_STRING")
+    );
+    assert!(
+        matches!(&errors[1], ConfigError::InvalidEnvironment { key, .. } if key == "MAP_TEST_INT")
+    );
+    assert!(
+        matches!(&errors[2], ConfigError::InvalidEnvironment { key, .. } if key == "MAP_TEST_OPTIONAL")
+    );
+}
+
+#[test]
+fn test_new_from_map_ignores_process_env() {
+    // TEST_STRING comes from test.env in other tests, it must not leak in here
+    dotenvy::from_filename("./test.env").ok();
+
+    let mut map = HashMap::new();
+    map.insert("MAP_TEST_STRING".to_string(), "isolated".to_string());
+
+    let config = MapConfig::new_from_map(&map).unwrap();
+    assert_eq!(config.string, "isolated");
+    assert_eq!(config.int, 42);
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "serde")]
+
+use config_loadr::{ConfigError, define_config};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    pub attempts: u32,
+    pub backoff_ms: u64,
+}
+
+define_config! {
+    #[derive(Debug)]
+    pub struct JsonConfig {
+        #[field(env = "JSON_RETRY", doc = "Retry policy", default = RetryPolicy { attempts: 3, backoff_ms: 100 }, json)]
+        pub retry: RetryPolicy,
+
+        // A Vec would be a list field without `json`
+        #[field(env = "JSON_ROUTES", doc = "Routing table", optional, json)]
+        pub routes: Option<Vec<String>>,
+    }
+}
+
+#[test]
+fn test_parses_json() {
+    let config = JsonConfig::from_iter([
+        ("JSON_RETRY", r#"{"attempts": 5, "backoff_ms": 250}"#),
+        ("JSON_ROUTES", r#"["/a", "/b,c"]"#),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        config.retry,
+        RetryPolicy {
+            attempts: 5,
+            backoff_ms: 250
+        }
+    );
+    assert_eq!(config.routes.unwrap(), ["/a", "/b,c"]);
+}
+
+#[test]
+fn test_reports_line_and_column() {
+    let errors = JsonConfig::from_iter([("JSON_RETRY", r#"{"attempts": 5}"#)]).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::InvalidValue { key, reason, example, .. }
+            if key == "JSON_RETRY"
+                && reason == "invalid JSON at line 1, column 15: missing field `backoff_ms`"
+                && example.as_deref() == Some(r#"{"attempts":3,"backoff_ms":100}"#)
+    ));
+}
+
+#[test]
+fn test_docs_render_default_as_json() {
+    let path = std::env::temp_dir().join(format!("config-loadr-json-{}.md", std::process::id()));
+    JsonConfig::builder_for_docs().write_docs(&path).unwrap();
+    let docs = std::fs::read_to_string(&path).unwrap();
+    assert!(docs.contains(r#"{"attempts":3,"backoff_ms":100}"#));
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,109 @@
+use config_loadr::{ConfigError, define_config};
+use std::collections::{BTreeSet, HashSet};
+
+define_config! {
+    #[derive(Debug)]
+    pub struct ListConfig {
+        #[field(env = "LIST_ORIGINS", doc = "Allowed CORS origins", example = vec!["https://example.com".to_string()], required)]
+        pub origins: Vec<String>,
+
+        #[field(env = "LIST_PORTS", doc = "Ports to listen on", default = vec![80u16, 443])]
+        pub ports: Vec<u16>,
+
+        #[field(env = "LIST_ROLES", doc = "Admin roles", separator = ";", default = HashSet::new())]
+        pub roles: HashSet<String>,
+
+        #[field(env = "LIST_REGIONS", doc = "Enabled regions", separator = "|", optional)]
+        pub regions: Option<BTreeSet<String>>,
+    }
+}
+
+define_config! {
+    pub struct SetDocsConfig {
+        #[field(env = "LIST_TAGS", doc = "Tags", default = HashSet::from(["d", "a", "c", "b"].map(String::from)))]
+        pub tags: HashSet<String>,
+    }
+}
+
+#[test]
+fn test_parses_lists() {
+    let config = ListConfig::from_iter([
+        ("LIST_ORIGINS", "https://a.example, https://b.example"),
+        ("LIST_ROLES", "admin;ops;admin"),
+        ("LIST_REGIONS", "eu | us"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.origins, ["https://a.example", "https://b.example"]);
+    assert_eq!(config.ports, [80, 443]);
+    assert_eq!(
+        config.roles,
+        HashSet::from(["admin".to_string(), "ops".to_string()])
+    );
+    assert_eq!(
+        config.regions,
+        Some(BTreeSet::from(["eu".to_string(), "us".to_string()]))
+    );
+}
+
+#[test]
+fn test_reports_failing_element() {
+    let errors =
+        ListConfig::from_iter([("LIST_ORIGINS", "x"), ("LIST_PORTS", "80,https,443")]).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::InvalidValue { key, reason, example, .. }
+            if key == "LIST_PORTS"
+                && reason == "element at index 1 ('https'): invalid digit found in string"
+                && example.as_deref() == Some("80,443")
+    ));
+}
+
+#[test]
+fn test_docs_render_default_list() {
+    let builder = ListConfig::builder_for_docs();
+    let defaults: Vec<&str> = builder
+        .fields()
+        .iter()
+        .map(|field| field.default_str.as_str())
+        .collect();
+
+    assert_eq!(defaults, ["https://example.com", "80,443", "", ""]);
+    assert_eq!(ListConfig::metadata().ports.default, [80, 443]);
+}
+
+#[test]
+fn test_docs_sort_sets() {
+    let builder = SetDocsConfig::builder_for_docs();
+    assert_eq!(builder.fields()[0].default_str, "a,b,c,d");
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_file_arrays_are_joined() {
+    use config_loadr::{ConfigBuilder, FileLayer};
+    use std::collections::HashMap;
+
+    let file = FileLayer::toml_str(
+        "config.toml",
+        "origins = [\"https://a.example\", \"https://b.example\"]\nports = [8080, 8443]\nroles = [\"admin\"]\n",
+    );
+    let config =
+        ListConfig::new_with_builder(ConfigBuilder::with_source(HashMap::new()).with_file(file))
+            .unwrap();
+    assert_eq!(config.origins, ["https://a.example", "https://b.example"]);
+    assert_eq!(config.ports, [8080, 8443]);
+    assert_eq!(config.roles, HashSet::from(["admin".to_string()]));
+
+    // An element holding the separator can't be joined
+    let file = FileLayer::toml_str("config.toml", "origins = [\"a,b\"]\n");
+    let errors =
+        ListConfig::new_with_builder(ConfigBuilder::with_source(HashMap::new()).with_file(file))
+            .unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        ConfigError::InvalidFileValue { key, value, .. } if key == "origins" && value == "<array>"
+    ));
+}
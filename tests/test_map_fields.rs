@@ -0,0 +1,48 @@
+use config_loadr::{ConfigError, define_config};
+use std::collections::{BTreeMap, HashMap};
+
+define_config! {
+    #[derive(Debug)]
+    pub struct MapConfig {
+        #[field(env = "MAP_RATE_LIMITS", doc = "Requests per second by tenant", default = BTreeMap::from([("acme".to_string(), 100u32)]))]
+        pub rate_limits: BTreeMap<String, u32>,
+
+        #[field(env = "MAP_HEADERS", doc = "Extra response headers", pair_separator = ";", kv_separator = ":", optional)]
+        pub headers: Option<HashMap<String, String>>,
+    }
+}
+
+#[test]
+fn test_parses_maps() {
+    let config = MapConfig::from_iter([
+        ("MAP_RATE_LIMITS", "acme=10, globex = 20"),
+        ("MAP_HEADERS", "x-frame-options: DENY; x-token: a:b"),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        config.rate_limits,
+        BTreeMap::from([("acme".to_string(), 10), ("globex".to_string(), 20)])
+    );
+    let headers = config.headers.unwrap();
+    assert_eq!(headers["x-frame-options"], "DENY");
+    assert_eq!(headers["x-token"], "a:b");
+
+    let config = MapConfig::from_iter(Vec::<(&str, &str)>::new()).unwrap();
+    assert_eq!(config.rate_limits["acme"], 100);
+    assert_eq!(config.headers, None);
+}
+
+#[test]
+fn test_reports_duplicate_key() {
+    let errors = MapConfig::from_iter([("MAP_RATE_LIMITS", "acme=10,acme=20")]).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::InvalidValue { key, reason, example, .. }
+            if key == "MAP_RATE_LIMITS"
+                && reason == "pair at index 1 ('acme=20'): duplicate key 'acme', already set at index 0"
+                && example.as_deref() == Some("acme=100")
+    ));
+}
@@ -0,0 +1,138 @@
+use config_loadr::{ConfigBuilder, ConfigError, FileLayer, Load, Provenance, define_config};
+use std::collections::HashMap;
+
+define_config! {
+    #[derive(Debug)]
+    pub struct DbConfig {
+        #[field(env = "URL", doc = "Database URL", example = "postgresql://localhost/db".to_string(), required)]
+        pub url: String,
+
+        #[field(env = "POOL_SIZE", doc = "Connection pool size", default = 5u32)]
+        pub pool_size: u32,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    pub struct CacheConfig {
+        #[field(env = "TTL", doc = "Cache TTL in seconds", default = 60u64)]
+        pub ttl: u64,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    pub struct AppConfig {
+        #[field(env = "APP_PORT", doc = "Server port", default = 8080u16)]
+        pub port: u16,
+
+        #[field(nested, prefix = "DB_")]
+        pub db: DbConfig,
+
+        #[field(nested, prefix = "CACHE_")]
+        pub cache: CacheConfig,
+    }
+}
+
+define_config! {
+    #[config(prefix = "SVC_")]
+    pub struct PrefixedAppConfig {
+        #[field(nested, prefix = "DB_")]
+        pub db: DbConfig,
+    }
+}
+
+#[test]
+fn test_nested_fields_use_prefix() {
+    let config = AppConfig::from_iter([
+        ("DB_URL", "postgresql://db/app"),
+        ("CACHE_TTL", "120"),
+        ("URL", "ignored"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.db.url, "postgresql://db/app");
+    assert_eq!(config.db.pool_size, 5);
+    assert_eq!(config.cache.ttl, 120);
+}
+
+#[test]
+fn test_nested_errors_are_collected_with_path() {
+    colored::control::set_override(false);
+
+    let errors = AppConfig::from_iter([("APP_PORT", "x"), ("CACHE_TTL", "soon")]).unwrap_err();
+    assert_eq!(errors.len(), 3);
+
+    assert!(matches!(&errors[0], ConfigError::InvalidEnvironment { key, .. } if key == "APP_PORT"));
+    let ConfigError::Nested { path, error } = &errors[1] else {
+        panic!("expected a nested error, got {:?}", errors[1]);
+    };
+    assert_eq!(path, "db.url");
+    assert!(matches!(&**error, ConfigError::MissingEnvVar { key, .. } if key == "DB_URL"));
+    assert!(errors[1].to_string().starts_with("db.url / DB_URL:"));
+    assert!(matches!(&errors[2], ConfigError::Nested { path, .. } if path == "cache.ttl"));
+}
+
+#[test]
+fn test_nested_metadata() {
+    let metadata = AppConfig::metadata();
+    assert_eq!(metadata.port.key, "APP_PORT");
+    assert_eq!(metadata.db.url.key, "DB_URL");
+    assert_eq!(metadata.db.pool_size.default, 5);
+    assert_eq!(metadata.cache.ttl.key, "CACHE_TTL");
+
+    // The child's own metadata is unprefixed
+    assert_eq!(DbConfig::metadata().url.key, "URL");
+    assert_eq!(PrefixedAppConfig::metadata().db.url.key, "SVC_DB_URL");
+}
+
+#[test]
+fn test_nested_docs_show_path() {
+    let builder = AppConfig::builder_for_docs();
+    let fields: Vec<(Option<&str>, &str)> = builder
+        .fields()
+        .iter()
+        .map(|field| (field.name.as_deref(), field.key.as_str()))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            (Some("port"), "APP_PORT"),
+            (Some("db.url"), "DB_URL"),
+            (Some("db.pool_size"), "DB_POOL_SIZE"),
+            (Some("cache.ttl"), "CACHE_TTL"),
+        ]
+    );
+
+    let path = std::env::temp_dir().join(format!("config-loadr-nested-{}.md", std::process::id()));
+    builder.write_docs(&path).unwrap();
+    let docs = std::fs::read_to_string(&path).unwrap();
+    assert!(docs.contains("| Field | Variable |"));
+    assert!(docs.contains("| db.url | DB_URL |"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_nested_fields_read_file_tables() {
+    let source = HashMap::from([("SVC_DB_POOL_SIZE".to_string(), "9".to_string())]);
+    let file = FileLayer::from_pairs("app.toml", [("db.url", "postgresql://file/db")]);
+
+    let mut builder = ConfigBuilder::with_source(&source).with_file(file);
+    let config = PrefixedAppConfig::load_into(&mut builder).unwrap();
+    assert_eq!(config.db.url, "postgresql://file/db");
+    assert_eq!(config.db.pool_size, 9);
+
+    let report = builder.provenance();
+    assert_eq!(
+        report.get("db.url").unwrap().provenance,
+        Provenance::File {
+            path: "app.toml".into(),
+            key: "db.url".to_string()
+        }
+    );
+    assert_eq!(
+        report.get("SVC_DB_POOL_SIZE").unwrap().value.as_deref(),
+        Some("9")
+    );
+}
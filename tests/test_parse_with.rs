@@ -0,0 +1,75 @@
+use config_loadr::{ConfigError, define_config};
+use std::time::Duration;
+
+// Duration has neither FromStr nor Display
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let seconds = value
+        .strip_suffix('s')
+        .ok_or_else(|| format!("expected a number of seconds like 30s, got '{}'", value))?;
+    seconds
+        .parse()
+        .map(Duration::from_secs)
+        .map_err(|e| format!("invalid seconds: {}", e))
+}
+
+fn display_duration(value: &Duration) -> String {
+    format!("{}s", value.as_secs())
+}
+
+fn parse_hex(value: &str) -> Result<u32, std::num::ParseIntError> {
+    u32::from_str_radix(value.trim_start_matches("0x"), 16)
+}
+
+define_config! {
+    #[derive(Debug)]
+    pub struct ParseWithConfig {
+        #[field(env = "PW_TIMEOUT", doc = "Request timeout", default = Duration::from_secs(30), parse_with = parse_duration, display_with = display_duration)]
+        pub timeout: Duration,
+
+        #[field(env = "PW_IDLE", doc = "Idle timeout", example = Duration::from_secs(300), required, parse_with = parse_duration, display_with = display_duration)]
+        pub idle: Duration,
+
+        #[field(env = "PW_MASK", doc = "Feature mask", optional, parse_with = parse_hex)]
+        pub mask: Option<u32>,
+    }
+}
+
+#[test]
+fn test_uses_custom_parser() {
+    let config = ParseWithConfig::from_iter([("PW_IDLE", "60s"), ("PW_MASK", "0x1f")]).unwrap();
+
+    assert_eq!(config.timeout, Duration::from_secs(30));
+    assert_eq!(config.idle, Duration::from_secs(60));
+    assert_eq!(config.mask, Some(31));
+}
+
+#[test]
+fn test_reports_parser_error() {
+    let errors = ParseWithConfig::from_iter([("PW_IDLE", "1m"), ("PW_TIMEOUT", "xs")]).unwrap_err();
+
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::InvalidValue { key, reason, example, .. }
+            if key == "PW_TIMEOUT"
+                && reason == "invalid seconds: invalid digit found in string"
+                && example.as_deref() == Some("30s")
+    ));
+    assert!(matches!(
+        &errors[1],
+        ConfigError::InvalidValue { key, reason, .. }
+            if key == "PW_IDLE" && reason == "expected a number of seconds like 30s, got '1m'"
+    ));
+}
+
+#[test]
+fn test_docs_use_display_with() {
+    let builder = ParseWithConfig::builder_for_docs();
+    let defaults: Vec<&str> = builder
+        .fields()
+        .iter()
+        .map(|field| field.default_str.as_str())
+        .collect();
+
+    assert_eq!(defaults, ["30s", "300s", ""]);
+}
@@ -0,0 +1,59 @@
+use config_loadr::{ConfigError, define_config};
+
+define_config! {
+    #[derive(Debug)]
+    #[config(prefix = "BILLING_")]
+    pub struct BillingConfig {
+        #[field(env = "PORT", doc = "Billing service port", default = 8080u16)]
+        pub port: u16,
+
+        #[field(env = "DATABASE_URL", doc = "Billing database", example = "postgresql://localhost/billing".to_string(), required)]
+        pub database_url: String,
+    }
+}
+
+#[test]
+fn test_prefixed_keys_are_loaded() {
+    let config = BillingConfig::from_iter([
+        ("BILLING_PORT", "9000"),
+        ("BILLING_DATABASE_URL", "postgresql://db/billing"),
+        ("PORT", "1"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.port, 9000);
+    assert_eq!(config.database_url, "postgresql://db/billing");
+}
+
+#[test]
+fn test_unprefixed_keys_are_ignored() {
+    let errors =
+        BillingConfig::from_iter([("DATABASE_URL", "postgresql://db/billing")]).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::MissingEnvVar { key, .. } if key == "BILLING_DATABASE_URL"
+    ));
+}
+
+#[test]
+fn test_metadata_and_docs_use_prefix() {
+    let metadata = BillingConfig::metadata();
+    assert_eq!(metadata.port.key, "BILLING_PORT");
+    assert_eq!(metadata.database_url.key, "BILLING_DATABASE_URL");
+
+    let builder = BillingConfig::builder_for_docs();
+    let keys: Vec<&str> = builder
+        .fields()
+        .iter()
+        .map(|field| field.key.as_str())
+        .collect();
+    assert_eq!(keys, ["BILLING_PORT", "BILLING_DATABASE_URL"]);
+
+    let path = std::env::temp_dir().join(format!("config-loadr-prefix-{}.md", std::process::id()));
+    builder.write_docs(&path).unwrap();
+    let docs = std::fs::read_to_string(&path).unwrap();
+    assert!(docs.contains("| BILLING_PORT |"));
+    std::fs::remove_file(&path).unwrap();
+}
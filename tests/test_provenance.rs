@@ -0,0 +1,94 @@
+mod common;
+
+use common::temp_dir;
+use config_loadr::{ConfigBuilder, Load, Provenance, define_config};
+use std::collections::HashMap;
+
+define_config! {
+    pub struct ProvenanceConfig {
+        #[field(env = "PROVENANCE_TEST_URL", doc = "Service URL", example = "http://localhost".to_string(), required)]
+        pub url: String,
+
+        #[field(env = "PROVENANCE_TEST_PORT", doc = "Server port", default = 8080u16)]
+        pub port: u16,
+
+        #[field(env = "PROVENANCE_TEST_WORKERS", doc = "Worker count", optional)]
+        pub workers: Option<u32>,
+    }
+}
+
+define_config! {
+    #[config(dotenv = "tests/fixtures/provenance.env")]
+    pub struct DotenvProvenanceConfig {
+        #[field(env = "PROVENANCE_TEST_DOTENV", doc = "Loaded from a dotenv file", default = 0u8)]
+        pub value: u8,
+    }
+}
+
+#[test]
+fn test_provenance_after_load() {
+    let source = HashMap::from([(
+        "PROVENANCE_TEST_URL".to_string(),
+        "http://example.com".to_string(),
+    )]);
+    let mut builder = ConfigBuilder::with_source(&source);
+    ProvenanceConfig::load_into(&mut builder).unwrap();
+
+    let report = builder.provenance();
+    assert_eq!(report.fields().len(), 3);
+
+    let url = report.get("PROVENANCE_TEST_URL").unwrap();
+    assert_eq!(url.provenance, Provenance::Source);
+    assert_eq!(url.value.as_deref(), Some("http://example.com"));
+
+    let port = report.get("port").unwrap();
+    assert_eq!(port.provenance, Provenance::Default);
+    assert_eq!(port.value.as_deref(), Some("8080"));
+
+    let workers = report.get("workers").unwrap();
+    assert_eq!(workers.provenance, Provenance::Missing);
+
+    assert!(report.get("UNKNOWN").is_none());
+    assert!(report.to_string().starts_with("KEY"));
+}
+
+#[test]
+fn test_provenance_points_at_dotenv_line() {
+    let (config, report) = DotenvProvenanceConfig::new_with_provenance().unwrap();
+    assert_eq!(config.value, 7);
+
+    let path = std::env::current_dir()
+        .unwrap()
+        .join("tests/fixtures/provenance.env");
+    assert_eq!(
+        report.get("value").unwrap().provenance,
+        Provenance::Dotenv { path, line: 3 }
+    );
+}
+
+#[test]
+fn test_dotenv_provenance_through_builder() {
+    let dir = temp_dir("provenance", &[(".env", b"PROVENANCE_TEST_MANUAL=9\n")]);
+    let path = dir.join(".env");
+
+    let mut builder = ConfigBuilder::new();
+    builder.set_dotenv(config_loadr::dotenv::load_file(&path, false).unwrap());
+    assert_eq!(
+        builder.or_default("PROVENANCE_TEST_MANUAL", "Manual", 0u8),
+        Some(9)
+    );
+    assert_eq!(
+        builder.provenance().get("PROVENANCE_TEST_MANUAL").unwrap().provenance,
+        Provenance::Dotenv { path, line: 1 }
+    );
+
+    // Without the loaded files the builder only knows the environment
+    let mut builder = ConfigBuilder::new();
+    let _ = builder.or_default("PROVENANCE_TEST_MANUAL", "Manual", 0u8);
+    assert_eq!(
+        builder.provenance().get("PROVENANCE_TEST_MANUAL").unwrap().provenance,
+        Provenance::Env
+    );
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,96 @@
+use config_loadr::{ConfigError, define_config};
+
+define_config! {
+    #[derive(Debug)]
+    pub struct WorkerConfig {
+        #[field(env = "HOST", doc = "Upstream host", example = "localhost".to_string(), required)]
+        pub host: String,
+
+        #[field(env = "PORT", doc = "Upstream port", default = 80u16)]
+        pub port: u16,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    pub struct PoolConfig {
+        #[field(env = "POOL_NAME", doc = "Pool name", default = "default".to_string())]
+        pub name: String,
+
+        #[field(repeated, prefix = "WORKER_")]
+        pub workers: Vec<WorkerConfig>,
+    }
+}
+
+#[test]
+fn test_loads_contiguous_groups() {
+    let config = PoolConfig::from_iter([
+        ("WORKER_0_HOST", "a.internal"),
+        ("WORKER_1_HOST", "b.internal"),
+        ("WORKER_1_PORT", "8081"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.workers.len(), 2);
+    assert_eq!(config.workers[0].host, "a.internal");
+    assert_eq!(config.workers[0].port, 80);
+    assert_eq!(config.workers[1].host, "b.internal");
+    assert_eq!(config.workers[1].port, 8081);
+}
+
+#[test]
+fn test_no_groups_is_empty() {
+    let config = PoolConfig::from_iter([("WORKER_HOST", "x"), ("WORKER_01_HOST", "y")]).unwrap();
+    assert!(config.workers.is_empty());
+}
+
+#[test]
+fn test_errors_name_indexed_key() {
+    let errors = PoolConfig::from_iter([
+        ("WORKER_0_HOST", "a.internal"),
+        ("WORKER_1_HOST", "b.internal"),
+        ("WORKER_1_PORT", "eighty"),
+    ])
+    .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    let ConfigError::Nested { path, error } = &errors[0] else {
+        panic!("expected a nested error, got {:?}", errors[0]);
+    };
+    assert_eq!(path, "workers.1.port");
+    assert!(
+        matches!(&**error, ConfigError::InvalidEnvironment { key, .. } if key == "WORKER_1_PORT")
+    );
+}
+
+#[test]
+fn test_gap_in_indices_is_reported() {
+    let errors = PoolConfig::from_iter([
+        ("WORKER_0_HOST", "a.internal"),
+        ("WORKER_2_HOST", "c.internal"),
+    ])
+    .unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::GroupIndexGap { prefix, missing: 1, found: 2 } if prefix == "WORKER_"
+    ));
+}
+
+#[test]
+fn test_docs_and_metadata_show_pattern() {
+    assert_eq!(PoolConfig::metadata().workers.host.key, "WORKER_<n>_HOST");
+
+    let builder = PoolConfig::builder_for_docs();
+    let keys: Vec<&str> = builder
+        .fields()
+        .iter()
+        .map(|field| field.key.as_str())
+        .collect();
+    assert_eq!(keys, ["POOL_NAME", "WORKER_<n>_HOST", "WORKER_<n>_PORT"]);
+    assert_eq!(
+        builder.fields()[1].name.as_deref(),
+        Some("workers.<n>.host")
+    );
+}
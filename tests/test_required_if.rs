@@ -0,0 +1,144 @@
+use config_loadr::{ConfigError, define_config};
+
+define_config! {
+    #[derive(Debug)]
+    pub struct MailConfig {
+        #[field(env = "RI_EMAIL_ENABLED", doc = "Send emails", default = false)]
+        pub email_enabled: bool,
+
+        #[field(env = "RI_SMTP_HOST", doc = "SMTP server", required_if = "RI_EMAIL_ENABLED")]
+        pub smtp_host: Option<String>,
+
+        #[field(env = "RI_STORAGE", doc = "Storage backend", default = "local".to_string())]
+        pub storage: String,
+
+        #[field(env = "RI_BUCKET", doc = "S3 bucket", required_if(env = "RI_STORAGE", eq = "s3"))]
+        pub bucket: Option<String>,
+
+        #[field(env = "RI_DATA_DIR", doc = "Local data directory", required_unless(env = "RI_STORAGE", eq = "s3"))]
+        pub data_dir: Option<String>,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    pub struct DefaultOnConfig {
+        #[field(env = "RID_EMAIL_ENABLED", doc = "Send emails", default = true)]
+        pub email_enabled: bool,
+
+        #[field(env = "RID_SMTP_HOST", doc = "SMTP server", required_if = "RID_EMAIL_ENABLED")]
+        pub smtp_host: Option<String>,
+    }
+}
+
+#[test]
+fn test_condition_not_met() {
+    let config = MailConfig::from_iter([("RI_DATA_DIR", "/var/lib/app")]).unwrap();
+    assert_eq!(config.smtp_host, None);
+    assert_eq!(config.bucket, None);
+
+    let config = MailConfig::from_iter([
+        ("RI_EMAIL_ENABLED", "false"),
+        ("RI_STORAGE", "s3"),
+        ("RI_BUCKET", "b"),
+    ])
+    .unwrap();
+    assert_eq!(config.bucket.as_deref(), Some("b"));
+    assert_eq!(config.data_dir, None);
+}
+
+#[test]
+fn test_condition_met_reports_missing() {
+    let errors =
+        MailConfig::from_iter([("RI_EMAIL_ENABLED", "true"), ("RI_STORAGE", "s3")]).unwrap_err();
+
+    let missing: Vec<(&str, &str)> = errors
+        .iter()
+        .map(|error| match error {
+            ConfigError::MissingConditional { key, condition, .. } => {
+                (key.as_str(), condition.as_str())
+            }
+            other => panic!("unexpected error: {}", other),
+        })
+        .collect();
+    assert_eq!(
+        missing,
+        [
+            ("RI_SMTP_HOST", "RI_EMAIL_ENABLED is true"),
+            ("RI_BUCKET", "RI_STORAGE is 's3'"),
+        ]
+    );
+
+    let errors = MailConfig::from_iter(Vec::<(&str, &str)>::new()).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        ConfigError::MissingConditional { key, condition, .. }
+            if key == "RI_DATA_DIR" && condition == "RI_STORAGE is not 's3'"
+    ));
+}
+
+#[test]
+fn test_condition_reads_default() {
+    let errors = DefaultOnConfig::from_iter(Vec::<(&str, &str)>::new()).unwrap_err();
+    assert!(matches!(
+        &errors[..],
+        [ConfigError::MissingConditional { key, .. }] if key == "RID_SMTP_HOST"
+    ));
+
+    let config = DefaultOnConfig::from_iter([("RID_EMAIL_ENABLED", "false")]).unwrap();
+    assert_eq!(config.smtp_host, None);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_condition_reads_file_layer() {
+    use config_loadr::{ConfigBuilder, FileLayer};
+    use std::collections::HashMap;
+
+    let file = FileLayer::toml_str("config.toml", "email_enabled = true\n");
+    let builder = ConfigBuilder::with_source(HashMap::new()).with_file(file);
+    let errors = MailConfig::new_with_builder(builder).unwrap_err();
+    assert!(errors.iter().any(|error| matches!(
+        error,
+        ConfigError::MissingConditional { key, .. } if key == "RI_SMTP_HOST"
+    )));
+
+    let file = FileLayer::toml_str("config.toml", "email_enabled = true\nsmtp_host = \"mail\"\n");
+    let builder = ConfigBuilder::with_source(HashMap::from([(
+        "RI_DATA_DIR".to_string(),
+        "/var/lib/app".to_string(),
+    )]))
+    .with_file(file);
+    let config = MailConfig::new_with_builder(builder).unwrap();
+    assert_eq!(config.smtp_host.as_deref(), Some("mail"));
+}
+
+#[test]
+fn test_docs_show_condition() {
+    let builder = MailConfig::builder_for_docs();
+    let conditions: Vec<Option<String>> = builder
+        .fields()
+        .iter()
+        .map(|field| field.condition.as_ref().map(ToString::to_string))
+        .collect();
+    let conditions: Vec<Option<&str>> = conditions.iter().map(Option::as_deref).collect();
+    assert_eq!(
+        conditions,
+        [
+            None,
+            Some("RI_EMAIL_ENABLED is true"),
+            None,
+            Some("RI_STORAGE is 's3'"),
+            Some("RI_STORAGE is not 's3'"),
+        ]
+    );
+
+    let path = std::env::temp_dir().join(format!(
+        "config-loadr-required-if-{}.md",
+        std::process::id()
+    ));
+    builder.write_docs(&path).unwrap();
+    let docs = std::fs::read_to_string(&path).unwrap();
+    assert!(docs.contains("| RI_SMTP_HOST | If RI_EMAIL_ENABLED is true | SMTP server |"));
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::{remove_temp_file, temp_file};
+use config_loadr::{ConfigBuilder, ConfigError, FileResolver, Load, define_config};
+use std::collections::HashMap;
+
+define_config! {
+    #[derive(Debug)]
+    pub struct ResolvedConfig {
+        #[field(env = "RES_DB_PASSWORD", doc = "Database password", example = "secret".to_string(), required)]
+        pub db_password: String,
+
+        #[field(env = "RES_PORT", doc = "Server port", default = 8080u16)]
+        pub port: u16,
+
+        #[field(env = "RES_STORAGE_URL", doc = "Storage URL", optional)]
+        pub storage_url: Option<String>,
+
+        #[field(env = "RES_ADMIN_IDS", doc = "Admin user IDs", default = vec![1u32])]
+        pub admin_ids: Vec<u32>,
+    }
+}
+
+/// An in-process secret store for `secretref://` references
+fn vault(reference: &str) -> Result<String, String> {
+    match reference {
+        "prod/db#password" => Ok("hunter2".to_string()),
+        "prod/app#port" => Ok("not-a-port".to_string()),
+        "prod/app#admins" => Ok("1,s3cr3t-token".to_string()),
+        _ => Err(format!("no secret at {}", reference)),
+    }
+}
+
+fn source(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_references_are_resolved() {
+    let source = source(&[
+        ("RES_DB_PASSWORD", "secretref://prod/db#password"),
+        ("RES_STORAGE_URL", "s3://bucket/path"),
+    ]);
+    let builder = ConfigBuilder::with_source(&source).with_resolver("secretref", vault);
+    let config = ResolvedConfig::new_with_builder(builder).unwrap();
+
+    assert_eq!(config.db_password, "hunter2");
+    // Schemes without a resolver are plain values
+    assert_eq!(config.storage_url.as_deref(), Some("s3://bucket/path"));
+}
+
+#[test]
+fn test_resolution_failure_is_reported() {
+    let source = source(&[("RES_DB_PASSWORD", "secretref://prod/missing")]);
+    let builder = ConfigBuilder::with_source(&source).with_resolver("secretref", vault);
+    let errors = ResolvedConfig::new_with_builder(builder).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::UnresolvedSecret { key, reference, message, .. }
+            if key == "RES_DB_PASSWORD"
+                && reference == "secretref://prod/missing"
+                && message == "no secret at prod/missing"
+    ));
+}
+
+#[test]
+fn test_resolved_values_are_never_echoed() {
+    let source = source(&[
+        ("RES_DB_PASSWORD", "secretref://prod/db#password"),
+        ("RES_PORT", "secretref://prod/app#port"),
+    ]);
+    let mut builder = ConfigBuilder::with_source(&source).with_resolver("secretref", vault);
+    assert!(ResolvedConfig::load_into(&mut builder).is_none());
+
+    let report = builder.provenance().to_string();
+    assert!(report.contains("secretref://prod/db#password"));
+    assert!(!report.contains("hunter2"));
+
+    let errors = builder.finish().unwrap_err();
+    let output: String = errors.iter().map(ToString::to_string).collect();
+    assert!(output.contains("RES_PORT: Invalid value 'secretref://prod/app#port'"));
+    assert!(!output.contains("not-a-port"));
+}
+
+#[test]
+fn test_file_resolver() {
+    let path = temp_file("resolver-password", b"from-file\n");
+
+    let source = source(&[("RES_DB_PASSWORD", &format!("file://{}", path.display()))]);
+    let builder = ConfigBuilder::with_source(&source).with_resolver("file", FileResolver);
+    let config = ResolvedConfig::new_with_builder(builder);
+    remove_temp_file(&path);
+
+    assert_eq!(config.unwrap().db_password, "from-file");
+}
+
+#[test]
+fn test_parse_reason_of_resolved_values_is_redacted() {
+    let source = source(&[
+        ("RES_DB_PASSWORD", "hunter2"),
+        ("RES_ADMIN_IDS", "secretref://prod/app#admins"),
+    ]);
+    let builder = ConfigBuilder::with_source(&source).with_resolver("secretref", vault);
+    let errors = ResolvedConfig::new_with_builder(builder).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::InvalidValue { key, value, reason, .. }
+            if key == "RES_ADMIN_IDS"
+                && value == "secretref://prod/app#admins"
+                && reason == "[REDACTED]"
+    ));
+    let output = errors[0].to_string();
+    assert!(!output.contains("s3cr3t-token"));
+}
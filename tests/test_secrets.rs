@@ -0,0 +1,132 @@
+mod common;
+
+use config_loadr::{ConfigBuilder, ConfigError, Load, Provenance, Secret, define_config};
+use std::collections::HashMap;
+
+define_config! {
+    #[derive(Debug, Clone)]
+    pub struct SecretConfig {
+        #[field(env = "SEC_API_KEY", doc = "API key", example = Secret::new("sk-example".to_string()), required)]
+        pub api_key: Secret<String>,
+
+        #[field(env = "SEC_PIN", doc = "Admin PIN", default = 1234u32, secret)]
+        pub pin: u32,
+
+        #[field(env = "SEC_PORT", doc = "Server port", default = 8080u16)]
+        pub port: u16,
+    }
+}
+
+#[test]
+fn test_secret_values_load() {
+    let config =
+        SecretConfig::from_iter([("SEC_API_KEY", "sk-live-123"), ("SEC_PIN", "9876")]).unwrap();
+
+    assert_eq!(config.api_key.expose(), "sk-live-123");
+    assert_eq!(config.pin, 9876);
+
+    let dump = format!("{:?}", config);
+    assert_eq!(
+        dump,
+        "SecretConfig { api_key: [REDACTED], pin: [REDACTED], port: 8080 }"
+    );
+    assert!(!dump.contains("sk-live-123") && !dump.contains("9876"));
+}
+
+#[test]
+fn test_errors_are_redacted() {
+    let errors =
+        SecretConfig::from_iter([("SEC_PIN", "hunter2"), ("SEC_PORT", "http")]).unwrap_err();
+
+    let output: String = errors.iter().map(ToString::to_string).collect();
+    assert!(!output.contains("hunter2"));
+    assert!(!output.contains("sk-example"));
+    assert!(output.contains("SEC_PORT: Invalid value 'http'"));
+    assert!(matches!(
+        &errors[1],
+        ConfigError::InvalidEnvironment { key, value, example, .. }
+            if key == "SEC_PIN" && value == "[REDACTED]" && example.as_deref() == Some("[REDACTED]")
+    ));
+}
+
+#[test]
+fn test_docs_and_metadata_are_redacted() {
+    let builder = SecretConfig::builder_for_docs();
+    let defaults: Vec<&str> = builder
+        .fields()
+        .iter()
+        .map(|field| field.default_str.as_str())
+        .collect();
+    assert_eq!(defaults, ["[REDACTED]", "[REDACTED]", "8080"]);
+
+    let metadata = SecretConfig::metadata();
+    assert!(metadata.api_key.secret && metadata.pin.secret && !metadata.port.secret);
+    assert!(format!("{:?}", metadata.pin).contains("default: [REDACTED]"));
+    assert_eq!(metadata.api_key.default.expose(), "sk-example");
+}
+
+#[test]
+fn test_provenance_is_redacted() {
+    let source = HashMap::from([
+        ("SEC_API_KEY".to_string(), "sk-live-123".to_string()),
+        ("SEC_PIN".to_string(), "9876".to_string()),
+    ]);
+    let mut builder = ConfigBuilder::with_source(&source);
+    SecretConfig::load_into(&mut builder).unwrap();
+
+    let report = builder.provenance();
+    let field = report.get("SEC_API_KEY").unwrap();
+    assert_eq!(field.provenance, Provenance::Source);
+    assert_eq!(field.value.as_deref(), Some("[REDACTED]"));
+    assert_eq!(
+        report.get("pin").unwrap().value.as_deref(),
+        Some("[REDACTED]")
+    );
+    assert!(!report.to_string().contains("sk-live-123"));
+    assert!(!report.to_string().contains("9876"));
+}
+
+#[cfg(feature = "zeroize")]
+mod zeroizing {
+    use crate::common::{remove_temp_file, temp_file};
+    use config_loadr::{ConfigBuilder, Load, ZeroizingSecret, define_config};
+    use std::collections::HashMap;
+
+    define_config! {
+        #[derive(Debug)]
+        pub struct ZeroizingConfig {
+            #[field(env = "ZSEC_API_KEY", doc = "API key", example = ZeroizingSecret::new("sk-example".to_string()), required, file_fallback)]
+            pub api_key: ZeroizingSecret<String>,
+
+            // Plain secret fields still compile with the feature, they just aren't wiped on drop
+            #[field(env = "ZSEC_TOKEN", doc = "Token", optional, secret)]
+            pub token: Option<String>,
+        }
+    }
+
+    #[test]
+    fn test_zeroizing_secret_field_loads() {
+        let config = ZeroizingConfig::from_iter([("ZSEC_API_KEY", "sk-live-123")]).unwrap();
+        assert_eq!(config.api_key.expose(), "sk-live-123");
+        assert_eq!(config.token, None);
+        assert_eq!(
+            format!("{:?}", config),
+            "ZeroizingConfig { api_key: [REDACTED], token: [REDACTED] }"
+        );
+    }
+
+    #[test]
+    fn test_zeroizing_secret_field_from_file() {
+        let path = temp_file("zeroizing-api-key", b"sk-from-file\n");
+
+        let source: HashMap<String, String> =
+            [("ZSEC_API_KEY_FILE".to_string(), path.display().to_string())].into();
+        let mut builder = ConfigBuilder::with_source(&source);
+        let config = ZeroizingConfig::load_into(&mut builder);
+        remove_temp_file(&path);
+
+        assert_eq!(config.unwrap().api_key.into_inner(), "sk-from-file");
+        let report = builder.provenance().to_string();
+        assert!(!report.contains("sk-from-file"));
+    }
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::PairSource;
+use config_loadr::{Source, define_config};
+
+define_config! {
+    #[derive(Debug)]
+    pub struct SourceConfig {
+        #[field(env = "SOURCE_TEST_URL", doc = "Service URL", example = "http://localhost".to_string(), required)]
+        pub url: String,
+
+        #[field(env = "SOURCE_TEST_PORT", doc = "Server port", default = 8080u16)]
+        pub port: u16,
+
+        #[field(env = "SOURCE_TEST_TIMEOUT", doc = "Timeout in seconds", optional)]
+        pub timeout: Option<u64>,
+    }
+}
+
+#[test]
+fn test_new_from_custom_source() {
+    let source = PairSource(&[
+        ("SOURCE_TEST_URL", "http://example.com"),
+        ("SOURCE_TEST_TIMEOUT", "30"),
+    ]);
+
+    let config = SourceConfig::new_from(&source).unwrap();
+    assert_eq!(config.url, "http://example.com");
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.timeout, Some(30));
+}
+
+#[test]
+fn test_new_from_collects_all_errors() {
+    let source = PairSource(&[("SOURCE_TEST_PORT", "not-a-port")]);
+
+    let errors = SourceConfig::new_from(&source).unwrap_err();
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn test_load_from_trait_object() {
+    let source = PairSource(&[("SOURCE_TEST_URL", "http://dyn.example.com")]);
+    let source: &dyn Source = &source;
+
+    let config = SourceConfig::load_from(source);
+    assert_eq!(config.url, "http://dyn.example.com");
+    assert_eq!(config.timeout, None);
+}
+
+#[test]
+#[should_panic]
+fn test_load_from_panics_on_error() {
+    let _config = SourceConfig::load_from(&PairSource(&[]));
+}
@@ -0,0 +1,137 @@
+use config_loadr::{ConfigError, define_config};
+
+fn check_pool(config: &PoolConfig) -> Result<(), Vec<ConfigError>> {
+    let mut errors = Vec::new();
+    if config.min_pool > config.max_pool {
+        errors.push(ConfigError::Inconsistent {
+            keys: vec!["MIN_POOL".to_string(), "MAX_POOL".to_string()],
+            reason: format!(
+                "MIN_POOL ({}) must not exceed MAX_POOL ({})",
+                config.min_pool, config.max_pool
+            ),
+        });
+    }
+    if config.tls_cert.is_some() != config.tls_key.is_some() {
+        errors.push(ConfigError::Inconsistent {
+            keys: vec!["TLS_CERT".to_string(), "TLS_KEY".to_string()],
+            reason: "must be set together".to_string(),
+        });
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(validate = check_pool)]
+    pub struct PoolConfig {
+        #[field(env = "MIN_POOL", doc = "Minimum pool size", default = 1u32)]
+        pub min_pool: u32,
+
+        #[field(env = "MAX_POOL", doc = "Maximum pool size", default = 10u32)]
+        pub max_pool: u32,
+
+        #[field(env = "TLS_CERT", doc = "TLS certificate path", optional)]
+        pub tls_cert: Option<String>,
+
+        #[field(env = "TLS_KEY", doc = "TLS key path", optional)]
+        pub tls_key: Option<String>,
+    }
+}
+
+fn reject_silently(_: &SilentConfig) -> Result<(), Vec<ConfigError>> {
+    Err(Vec::new())
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(validate = reject_silently)]
+    pub struct SilentConfig {
+        #[field(env = "PORT", doc = "Port", default = 8080u16)]
+        pub port: u16,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    pub struct SilentParentConfig {
+        #[field(nested, prefix = "APP_")]
+        pub app: SilentConfig,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    pub struct ServiceConfig {
+        #[field(env = "NAME", doc = "Service name", example = "api".to_string(), required)]
+        pub name: String,
+
+        #[field(nested, prefix = "DB_")]
+        pub db: PoolConfig,
+    }
+}
+
+#[test]
+fn test_hook_accepts_valid_config() {
+    let config = PoolConfig::from_iter([("TLS_CERT", "cert.pem"), ("TLS_KEY", "key.pem")]).unwrap();
+    assert_eq!(config.tls_key.as_deref(), Some("key.pem"));
+}
+
+#[test]
+fn test_hook_errors_are_collected() {
+    let errors = PoolConfig::from_iter([("MIN_POOL", "20"), ("TLS_CERT", "cert.pem")]).unwrap_err();
+
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::Inconsistent { reason, .. } if reason == "MIN_POOL (20) must not exceed MAX_POOL (10)"
+    ));
+    assert!(matches!(
+        &errors[1],
+        ConfigError::Inconsistent { keys, .. } if keys == &["TLS_CERT", "TLS_KEY"]
+    ));
+}
+
+#[test]
+fn test_hook_waits_for_fields() {
+    // The hook only sees fully loaded configs, so only the parse error is reported
+    let errors =
+        PoolConfig::from_iter([("MIN_POOL", "many"), ("TLS_CERT", "cert.pem")]).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::InvalidEnvironment { key, .. } if key == "MIN_POOL"
+    ));
+}
+
+#[test]
+fn test_nested_hook_errors_have_path() {
+    let errors = ServiceConfig::from_iter([("DB_MIN_POOL", "20")]).unwrap_err();
+
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(&errors[0], ConfigError::MissingEnvVar { key, .. } if key == "NAME"));
+    assert!(matches!(
+        &errors[1],
+        ConfigError::Nested { path, error }
+            if path == "db" && matches!(**error, ConfigError::Inconsistent { .. })
+    ));
+}
+
+#[test]
+fn test_empty_hook_errors_still_fail() {
+    let errors = SilentConfig::from_iter([("PORT", "80")]).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&errors[0], ConfigError::Inconsistent { keys, .. } if keys.is_empty()));
+
+    let errors = SilentParentConfig::from_iter([("APP_PORT", "80")]).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::Nested { path, error }
+            if path == "app" && matches!(**error, ConfigError::Inconsistent { .. })
+    ));
+}
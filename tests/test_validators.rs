@@ -0,0 +1,126 @@
+use config_loadr::{ConfigError, define_config};
+
+define_config! {
+    #[derive(Debug)]
+    pub struct ValidatedConfig {
+        #[field(env = "VAL_PORT", doc = "Server port", default = 8080u16, range = 1..=65535)]
+        pub port: u16,
+
+        #[field(env = "VAL_DATABASE_URL", doc = "Database URL", example = "postgres://localhost/db".to_string(), required, min_len = 1)]
+        pub database_url: String,
+
+        #[field(env = "VAL_LOG_LEVEL", doc = "Log level", default = "info".to_string(), one_of = ["debug", "info", "warn"])]
+        pub log_level: String,
+
+        #[field(env = "VAL_HOSTS", doc = "Upstream hosts", default = vec!["localhost".to_string()], min_len = 1, max_len = 3)]
+        pub hosts: Vec<String>,
+    }
+}
+
+#[cfg(feature = "regex")]
+define_config! {
+    #[derive(Debug)]
+    pub struct RegionConfig {
+        #[field(env = "VAL_REGION", doc = "Deployment region", optional, matches = "[a-z]+-[a-z]+-[0-9]")]
+        pub region: Option<String>,
+    }
+}
+
+#[test]
+fn test_accepts_valid_values() {
+    let config = ValidatedConfig::from_iter([
+        ("VAL_DATABASE_URL", "postgres://db/app"),
+        ("VAL_LOG_LEVEL", "warn"),
+    ])
+    .unwrap();
+
+    assert_eq!(config.port, 8080);
+    assert_eq!(config.log_level, "warn");
+}
+
+#[test]
+fn test_reports_every_failed_constraint() {
+    let errors = ValidatedConfig::from_iter([
+        ("VAL_PORT", "0"),
+        ("VAL_DATABASE_URL", ""),
+        ("VAL_LOG_LEVEL", "trace"),
+        ("VAL_HOSTS", "a,b,c,d"),
+    ])
+    .unwrap_err();
+
+    let reasons: Vec<(&str, &str, &str)> = errors
+        .iter()
+        .map(|error| match error {
+            ConfigError::ValidationFailed {
+                key, value, reason, ..
+            } => (key.as_str(), value.as_str(), reason.as_str()),
+            other => panic!("unexpected error: {}", other),
+        })
+        .collect();
+    assert_eq!(
+        reasons,
+        [
+            ("VAL_PORT", "0", "must be in range 1..=65535"),
+            (
+                "VAL_DATABASE_URL",
+                "",
+                "must have a length of at least 1, got 0"
+            ),
+            (
+                "VAL_LOG_LEVEL",
+                "trace",
+                r#"must be one of ["debug", "info", "warn"]"#
+            ),
+            (
+                "VAL_HOSTS",
+                "a,b,c,d",
+                "must have a length of at most 3, got 4"
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_docs_list_constraints() {
+    let builder = ValidatedConfig::builder_for_docs();
+    let constraints: Vec<String> = builder
+        .fields()
+        .iter()
+        .map(|field| field.constraints.join(", "))
+        .collect();
+    assert_eq!(
+        constraints,
+        [
+            "range 1..=65535",
+            "min length 1",
+            r#"one of ["debug", "info", "warn"]"#,
+            "min length 1, max length 3",
+        ]
+    );
+
+    let path =
+        std::env::temp_dir().join(format!("config-loadr-validators-{}.md", std::process::id()));
+    builder.write_docs(&path).unwrap();
+    let docs = std::fs::read_to_string(&path).unwrap();
+    assert!(docs.contains("| Default/Example | Constraints |"));
+    assert!(docs.contains("| 8080 | range 1..=65535 |"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_matches_whole_value() {
+    let config = RegionConfig::from_iter([("VAL_REGION", "eu-west-1")]).unwrap();
+    assert_eq!(config.region.as_deref(), Some("eu-west-1"));
+
+    let errors = RegionConfig::from_iter([("VAL_REGION", "mars")]).unwrap_err();
+    assert!(matches!(
+        &errors[0],
+        ConfigError::ValidationFailed { key, reason, .. }
+            if key == "VAL_REGION" && reason == "must match `[a-z]+-[a-z]+-[0-9]`"
+    ));
+    assert_eq!(
+        RegionConfig::builder_for_docs().fields()[0].constraints,
+        ["matches `[a-z]+-[a-z]+-[0-9]`"]
+    );
+}
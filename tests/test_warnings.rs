@@ -0,0 +1,224 @@
+use config_loadr::{ConfigBuilder, ConfigError, ConfigWarning, Environment, Load, define_config};
+use std::collections::HashMap;
+
+define_config! {
+    #[derive(Debug)]
+    #[config(prefix = "WARN_", environment = environment)]
+    pub struct WarnConfig {
+        #[field(env = "ENVIRONMENT", doc = "Application environment", default = Environment::Dev)]
+        pub environment: Environment,
+
+        #[field(env = "DATABASE_URL", doc = "Database URL", example = "postgres://localhost/db".to_string(), required, aliases = ["DB_URL"], file_fallback)]
+        pub database_url: String,
+
+        #[field(env = "LOG_LEVEL", doc = "Log level", default = "debug".to_string())]
+        pub log_level: String,
+
+        #[field(env = "SMTP_HOST", doc = "SMTP server", required_if = "EMAIL_ENABLED")]
+        pub smtp_host: Option<String>,
+    }
+}
+
+/// An application's own environment type, unrelated to `config_loadr::Environment`
+mod app {
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Environment {
+        Local,
+        Hosted,
+    }
+
+    impl std::str::FromStr for Environment {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "local" => Ok(Environment::Local),
+                "hosted" => Ok(Environment::Hosted),
+                _ => Err(format!("unknown environment {}", s)),
+            }
+        }
+    }
+
+    impl std::fmt::Display for Environment {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(match self {
+                Environment::Local => "local",
+                Environment::Hosted => "hosted",
+            })
+        }
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(prefix = "WARN_OWN_")]
+    pub struct OwnEnvironmentConfig {
+        #[field(env = "ENVIRONMENT", doc = "Where the app runs", default = app::Environment::Local)]
+        pub environment: app::Environment,
+
+        #[field(env = "PROFILE", doc = "Deployment profile", default = Environment::Dev)]
+        pub profile: Environment,
+
+        #[field(env = "LOG_LEVEL", doc = "Log level", default = "debug".to_string())]
+        pub log_level: String,
+    }
+}
+
+define_config! {
+    pub struct WarnEnvConfig {
+        #[field(env = "WARN_ENV_TIMEOUT", doc = "Timeout in seconds", default = 30u64, aliases = ["WARN_ENV_OLD_TIMEOUT"])]
+        pub timeout: u64,
+    }
+}
+
+define_config! {
+    #[derive(Debug)]
+    #[config(dotenv = "tests/fixtures/base.env")]
+    pub struct StrictDotenvConfig {
+        #[field(env = "DOTENV_OPTIONS_BASE_ONLY", doc = "Only set in the base file", example = "x".to_string(), required)]
+        pub base_only: String,
+
+        #[field(env = "WARN_STRICT_TIMEOUT", doc = "Timeout in seconds", default = 30u64, aliases = ["WARN_STRICT_OLD_TIMEOUT"])]
+        pub timeout: u64,
+    }
+}
+
+fn source(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+    pairs
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+fn load(builder: ConfigBuilder<'_>) -> Result<(WarnConfig, Vec<ConfigWarning>), Vec<ConfigError>> {
+    let mut builder = builder;
+    let config = WarnConfig::load_into(&mut builder);
+    let warnings = builder.finish_with_warnings()?;
+    Ok((config.unwrap(), warnings))
+}
+
+#[test]
+fn test_no_warnings() {
+    let source = source(&[("WARN_DATABASE_URL", "postgres://db")]);
+    let (_, warnings) = load(ConfigBuilder::with_source(&source)).unwrap();
+    assert_eq!(warnings, vec![]);
+}
+
+#[test]
+fn test_deprecated_alias_warning() {
+    let source = source(&[("WARN_DB_URL", "postgres://db")]);
+    let (config, warnings) = load(ConfigBuilder::with_source(&source)).unwrap();
+
+    assert_eq!(config.database_url, "postgres://db");
+    assert_eq!(
+        warnings,
+        vec![ConfigWarning::DeprecatedAlias {
+            alias: "WARN_DB_URL".to_string(),
+            key: "WARN_DATABASE_URL".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_default_in_production_warning() {
+    let source = source(&[
+        ("WARN_ENVIRONMENT", "prod"),
+        ("WARN_DATABASE_URL", "postgres://db"),
+    ]);
+    let (_, warnings) = load(ConfigBuilder::with_source(&source)).unwrap();
+
+    assert_eq!(
+        warnings,
+        vec![ConfigWarning::DefaultInProduction {
+            key: "WARN_LOG_LEVEL".to_string(),
+            default: "debug".to_string(),
+            description: "Log level".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_default_in_production_is_opt_in() {
+    let source = source(&[
+        ("WARN_OWN_ENVIRONMENT", "hosted"),
+        ("WARN_OWN_PROFILE", "prod"),
+    ]);
+    let mut builder = ConfigBuilder::with_source(&source).strict();
+    let config = OwnEnvironmentConfig::load_into(&mut builder);
+    assert_eq!(builder.finish_with_warnings().unwrap(), vec![]);
+
+    let config = config.unwrap();
+    assert_eq!(config.environment, app::Environment::Hosted);
+    assert_eq!(config.profile, Environment::Prod);
+}
+
+#[test]
+fn test_unknown_key_warning() {
+    let source = source(&[
+        ("WARN_DATABASE_URL", "postgres://db"),
+        ("WARN_DATABASE_URL_FILE", "/run/secrets/db"),
+        ("WARN_LOG_LEVLE", "info"),
+        ("WARN_EMAIL_ENABLED", "false"),
+        ("OTHER_LOG_LEVEL", "info"),
+    ]);
+    let (_, warnings) = load(ConfigBuilder::with_source(&source)).unwrap();
+
+    assert_eq!(
+        warnings,
+        vec![ConfigWarning::UnknownKey {
+            key: "WARN_LOG_LEVLE".to_string(),
+            prefix: "WARN_".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_strict_mode_promotes_warnings() {
+    let source = source(&[("WARN_DB_URL", "postgres://db"), ("WARN_PORT", "8080")]);
+    let errors = load(ConfigBuilder::with_source(&source).strict()).unwrap_err();
+
+    assert_eq!(errors.len(), 2);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::Warning { warning: ConfigWarning::DeprecatedAlias { alias, .. } }
+            if alias == "WARN_DB_URL"
+    ));
+    assert!(matches!(
+        &errors[1],
+        ConfigError::Warning { warning: ConfigWarning::UnknownKey { key, .. } }
+            if key == "WARN_PORT"
+    ));
+}
+
+#[test]
+fn test_new_with_warnings() {
+    std::env::set_var("WARN_ENV_OLD_TIMEOUT", "60");
+    let (config, warnings) = WarnEnvConfig::new_with_warnings().unwrap();
+    std::env::remove_var("WARN_ENV_OLD_TIMEOUT");
+
+    assert_eq!(config.timeout, 60);
+    assert_eq!(
+        warnings,
+        vec![ConfigWarning::DeprecatedAlias {
+            alias: "WARN_ENV_OLD_TIMEOUT".to_string(),
+            key: "WARN_ENV_TIMEOUT".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_new_strict_loads_dotenv() {
+    let config = StrictDotenvConfig::new_strict().unwrap();
+    assert_eq!(config.base_only, "base");
+
+    std::env::set_var("WARN_STRICT_OLD_TIMEOUT", "60");
+    let errors = StrictDotenvConfig::new_strict().unwrap_err();
+    std::env::remove_var("WARN_STRICT_OLD_TIMEOUT");
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ConfigError::Warning { warning: ConfigWarning::DeprecatedAlias { alias, .. } }
+            if alias == "WARN_STRICT_OLD_TIMEOUT"
+    ));
+}